@@ -6,8 +6,8 @@
 //      - address of: get var's stk_pos and use "lea" to get the memory address
 //      - deref: currently blind trust towards the memory address that is being de-referenced, may seg faults to come!
 
-//  ❌ Stack Allocation:
-//      - calculate size of all variables declared in a scope.
+//  ✅ Stack Allocation:
+//      - calculate size of all variables declared in a function (deepest stk_pos reached).
 //          - "sub rsp, SIZE_OF_VARS_BYTES" <- point rsp to top of the stack!
 //          - SIZE_OF_VARS is always a multiple of "8"
 //      - base pointer points to stack address at the start of a function
@@ -18,7 +18,11 @@
 //  ❌ Global Variables:
 //       - stored in static memory ".data " section or ".bss" for zero-initialisation
 
-//  ❌ Functions:
+//  ✅ Program Entry:
+//      - "_start" sets up a stack frame, runs top level statements then calls "main".
+//      - exits with main's return value, or 0 if main is void.
+
+//  ✅ Functions:
 //       - https://www-users.cse.umn.edu/~smccaman/courses/8980/spring2020/lectures/03-x86-funcs-data-8up.pdf
 //       - Setup stackframe:
 //          - store current base pointer location
@@ -37,41 +41,75 @@
 //       - stack frames MUST have a 16 BYTE alignment
 //          - push extra bytes if not aligned?
 
-//  ❌ Calling FUNCTIONS:
-//      -"call _FUNC_NAME_"
-//      - return val in rax
+//  ✅ Floats:
+//      - SSE scalar instructions, values live in "xmm" registers, handed out like the general purpose ones.
+//      - literals are moved in as raw bits through a general purpose register, no ".data" section needed.
+//      - f32 <-> f64 with "cvtss2sd" / "cvtsd2ss", integers with "cvtsi2sd" / "cvttsd2si" (truncating).
+//      - float args arrive in "xmm0-7" and are returned in "xmm0", like the SysV ABI.
+
+//  ✅ For Loops:
+//      - 'end' and 'step' are evaluated once into hidden stack slots, the counter is a normal variable.
+//      - the loop ends when 'end - counter' is within a step, so 'counter + step' never overflows.
+
+//  ✅ Calling FUNCTIONS:
+//      - live scratch registers are saved on the stack around the call, the callee may use them all.
+//      - args are evaluated && pushed in order, then popped into "rdi, rsi, rdx, rcx, r8, r9" && "xmm0-7".
+//      - return val in rax, or xmm0 for floats.
 
 use crate::{
-    debug, err,
+    debug,
+    diagnostic::Diagnostic,
+    err,
     lex::{Token, TokenFlags, TokenKind},
     parse::{NodeExpr, NodeScope, NodeStmt, NodeTerm, AST},
-    semantic::{Byte, Checker, InitExpr, SemFn, Type},
+    semantic::{
+        AddressingMode, Byte, Checker, ExprData, ExprForm, InitExpr, SemFn, Type, TypeForm,
+        TypeMode,
+    },
+    span::Span,
 };
 use std::collections::HashMap;
 
 const LOG_DEBUG_INFO: bool = false;
-const SPACE: &'static str = "    ";
-const MSG: &'static str = "CODEGEN";
+const SPACE: &str = "    ";
+const CODE: u16 = 400;
+const MSG: &str = "CODEGEN";
+const XMM_REGISTERS: [&str; 16] = [
+    "xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7", "xmm8", "xmm9", "xmm10",
+    "xmm11", "xmm12", "xmm13", "xmm14", "xmm15",
+];
+const ARG_REGISTERS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+const FOR_STEP_EXIT: u8 = 101; // exit code of a 'for' loop entered with a step that isn't more than 0
 
 #[derive(Debug, Clone, PartialEq)]
 struct GenVariable {
     ident: Token,
     width: Byte,
     type_id: usize,
+    addr_mode: AddressingMode,
     stk_index: Byte,
 }
 
+// the jump targets of a loop, for a 'break' or 'continue' inside it.
+struct GenLoop {
+    label: Option<String>,
+    continue_label: String,
+    end_label: String,
+}
+
 struct CodeGenContext {
     reg_count: usize,
+    xmm_count: usize,
     label_count: usize,
     endif_label: String,
-    loop_end_label: String,
-    scope_allocations: Byte,
+    loops: Vec<GenLoop>, // innermost last
+    return_label: String,
+    return_float: Option<Byte>, // width of a float return value
+    frame_size: Byte,
 }
 
 pub struct Generator {
     stk_pos: Byte,
-    pos: (u32, u32),
     checker: Checker,
     ctx: CodeGenContext,
     stack: Vec<GenVariable>, // stack contains variables,
@@ -82,7 +120,6 @@ pub struct Generator {
 impl Generator {
     pub fn new(checker: Checker) -> Generator {
         Generator {
-            pos: (0, 0),
             stk_pos: 0,
             checker,
             stack: Vec::new(),
@@ -90,29 +127,111 @@ impl Generator {
             fn_map: HashMap::new(),
             ctx: CodeGenContext {
                 reg_count: 0,
+                xmm_count: 0,
                 label_count: 0,
-                scope_allocations: 0,
+                frame_size: 0,
                 endif_label: String::new(),
-                loop_end_label: String::new(),
+                loops: Vec::new(),
+                return_label: String::new(),
+                return_float: None,
             },
         }
     }
 
-    pub fn gen_asm(&mut self) -> Result<String, String> {
-        let mut asm = "global main\n".to_string();
+    pub fn gen_asm(&mut self) -> Result<String, Diagnostic> {
+        // top level statements run in '_start' before main, so generate them first.
+        let mut start_asm = String::new();
+        let mut fn_stmts = Vec::new();
         while !self.checker.ast.stmts.is_empty() {
-            let stmt = self.checker.ast.stmts.remove(0);
+            match self.checker.ast.stmts.remove(0) {
+                stmt @ NodeStmt::FnSemantics { .. } => fn_stmts.push(stmt),
+                stmt => start_asm += self.gen_top_level(stmt)?.as_str(),
+            }
+        }
+        let start_frame = self.gen_frame_alloc();
+
+        let exit_code = match self.checker.fn_map.get("main") {
+            Some(main) if main.return_type_id.is_some() => "rax",
+            _ => "0",
+        };
+        let mut asm = format!(
+            "global _start\n\
+             section .text\n\
+             _start:\n\
+             {SPACE}push rbp\n\
+             {SPACE}mov rbp, rsp\n\
+             {start_frame}\
+             {start_asm}\
+             {SPACE}call main\n\
+             {SPACE}mov rdi, {exit_code}\n\
+             {SPACE}mov rax, 60\n\
+             {SPACE}syscall\n"
+        );
+        for stmt in fn_stmts {
             asm += self.gen_top_level(stmt)?.as_str();
         }
         Ok(asm)
     }
 
-    fn gen_top_level(&mut self, stmt: NodeStmt) -> Result<String, String> {
+    fn gen_top_level(&mut self, stmt: NodeStmt) -> Result<String, Diagnostic> {
         match stmt {
-            // Create the potential code for a function, then store it in a map.
-            // on function calls: read the information and use it.
-            NodeStmt::FnSemantics { signature } => {
-                todo!("")
+            NodeStmt::FnSemantics { signature, .. } => {
+                let func = match self.checker.fn_map.get(&signature) {
+                    Some(func) => func.clone(),
+                    None => return err!("Function '{signature}' was never checked."),
+                };
+
+                // every function gets a fresh stack frame.
+                self.stack.clear();
+                self.var_map.clear();
+                self.stk_pos = 0;
+                self.ctx.frame_size = 0;
+
+                let label = self.gen_fn_label(&signature);
+                self.ctx.return_label = format!(".{label}_RETURN");
+                self.ctx.return_float = self.return_float(&func);
+
+                // first six integer args && eight float args are passed in registers,
+                // .. spill them into the frame.
+                let (mut int_args, mut float_args) = (0, 0);
+                let mut args_asm = String::new();
+                for arg in func.arg_semantics {
+                    let is_float = arg.addr_mode == AddressingMode::Primitive
+                        && self.float_type(arg.type_id).is_some();
+                    let stk_index = self.push_var(arg.ident, arg.width, arg.type_id, arg.addr_mode);
+                    let dest = self.gen_stk_access(stk_index, arg.width);
+                    match is_float {
+                        true if float_args < 8 => {
+                            let mov = self.gen_float_mov(arg.width);
+                            args_asm += &format!("{SPACE}{mov} {dest}, xmm{float_args}\n");
+                            float_args += 1;
+                        }
+                        false if int_args < ARG_REGISTERS.len() => {
+                            let arg_reg = self.fit_reg(ARG_REGISTERS[int_args], &dest);
+                            args_asm += &format!("{SPACE}mov {dest}, {arg_reg}\n");
+                            int_args += 1;
+                        }
+                        _ => {
+                            return err!("Too many arguments to pass in registers in '{signature}'")
+                        }
+                    }
+                }
+
+                let scope_asm = self.gen_scope(func.scope)?;
+                let frame = self.gen_frame_alloc();
+                let return_label = self.ctx.return_label.as_str();
+                Ok(format!(
+                    "{label}:\n\
+                     {SPACE}push rbp\n\
+                     {SPACE}mov rbp, rsp\n\
+                     {frame}\
+                     {args_asm}\
+                     {scope_asm}\
+                     {return_label}:\n\
+                     {SPACE}mov rsp, rbp\n\
+                     {SPACE}pop rbp\n\
+                     {SPACE}ret\n"
+                ))
             }
             _ => {
                 self.gen_stmt(stmt)
@@ -125,10 +244,20 @@ impl Generator {
     }
 
     // TODO: BYTE ARRAYS!
-    fn gen_stmt(&mut self, stmt: NodeStmt) -> Result<String, String> {
+    fn gen_stmt(&mut self, stmt: NodeStmt) -> Result<String, Diagnostic> {
         match stmt {
             NodeStmt::NakedScope(scope) => self.gen_scope(scope),
-            NodeStmt::Exit(expr) => {
+            // the result is computed && thrown away, its register is free straight after.
+            NodeStmt::Expr { expr, .. } | NodeStmt::Discard { expr, .. } => {
+                let is_float = self.float_width(&expr).is_some();
+                let asm = self.gen_expr(expr, None)?;
+                match is_float {
+                    true => self.release_xmm(),
+                    false => self.release_reg(),
+                }
+                Ok(asm)
+            }
+            NodeStmt::Exit { expr, .. } => {
                 let expr_asm = self.gen_expr(expr, Some("rdi"))?;
                 Ok(format!(
                     "; Exit Program\n\
@@ -139,65 +268,93 @@ impl Generator {
             }
             NodeStmt::VarSemantics(sem_var) => {
                 if self.get_var(sem_var.ident.as_str()).is_ok() {
-                    return err!("Re-Initialisation of a Variable:\n{sem_var:#?}");
+                    let name = sem_var.ident.as_str();
+                    return err!(
+                        sem_var.ident.span,
+                        "Re-Initialisation of a Variable: '{name}'"
+                    );
                 }
                 let name = sem_var.ident.clone();
-                let var = GenVariable {
-                    ident: sem_var.ident,
-                    stk_index: self.stk_pos + sem_var.width,
-                    type_id: sem_var.type_id,
-                    width: sem_var.width,
-                };
-
-                self.stk_pos += sem_var.width;
-                self.var_map
-                    .insert(var.ident.as_str().to_string(), self.stack.len());
-                self.stack.push(var);
+                let stk_index = self.push_var(
+                    sem_var.ident,
+                    sem_var.width,
+                    sem_var.type_id,
+                    sem_var.addr_mode,
+                );
 
                 let mut str = String::new();
                 if let InitExpr::Some(expr) = sem_var.init_expr {
-                    let stk_pos = self.gen_stk_access(self.stk_pos, sem_var.width);
+                    let stk_pos = self.gen_stk_access(stk_index, sem_var.width);
                     str += self.gen_expr(expr, Some(stk_pos.as_str()))?.as_str();
                 }
                 str.pop(); // remove '\n'
                 str += format!(" ; Ident('{}')\n", name.as_str()).as_str();
                 Ok(str)
             }
-            NodeStmt::Assign { ident, expr } => {
-                let var = self.get_var(ident.as_str())?;
-                let ans_reg = self.gen_stk_access(var.stk_index, var.width);
-                self.gen_expr(expr, Some(ans_reg.as_str()))
+            NodeStmt::Assign { target, expr, .. } => self.gen_assign(target, expr),
+            // 'p^ op= expr' loads 'p' once, a variable is just 'x = x op (expr)'.
+            NodeStmt::CompoundAssign {
+                target:
+                    NodeExpr::UnaryExpr {
+                        op: TokenKind::Ptr,
+                        operand,
+                        ..
+                    },
+                op,
+                expr,
+                ..
+            } => self.gen_ptr_compound_assign(*operand, op, expr),
+            NodeStmt::CompoundAssign {
+                target,
+                op,
+                expr,
+                span,
+            } => {
+                let binary = NodeExpr::BinaryExpr {
+                    op,
+                    lhs: Box::new(target.clone()),
+                    rhs: Box::new(expr),
+                    span,
+                };
+                self.gen_assign(target, binary)
             }
             NodeStmt::If {
                 condition,
                 scope,
                 branches,
+                ..
             } => {
                 // TODO(TOM): operand changes jump instruction, e.g je (jump if equal)
                 // .. .. do the inverse of the condition:
                 // .. .. .. if expr is false (0): jump to else[if] // end of if statement scope.
 
+                let mut endif_label = String::new();
                 let mut endif_jmp = String::new();
                 let mut endif_goto = String::new();
                 if !branches.is_empty() {
-                    self.ctx.endif_label = self.gen_label("END_IF");
-                    endif_goto = format!("{}:\n", self.ctx.endif_label.as_str());
-                    endif_jmp = format!("{SPACE}jmp {}\n", self.ctx.endif_label.as_str());
+                    endif_label = self.gen_label("END_IF");
+                    endif_goto = format!("{endif_label}:\n");
+                    endif_jmp = format!("{SPACE}jmp {endif_label}\n");
                 }
                 let false_label = self.gen_label("IF_FALSE");
 
                 let condition_asm = self.gen_expr(condition, None)?;
+                let cond_reg = self.get_reg(self.ctx.reg_count);
+                self.release_reg();
                 let scope_asm = self.gen_scope(scope)?;
 
+                // set after the scope, a nested 'if' would overwrite it.
+                let outer_endif_label = std::mem::replace(&mut self.ctx.endif_label, endif_label);
                 let mut branches_asm = String::new();
                 for branch in branches {
                     branches_asm += &self.gen_stmt(branch)?;
                 }
+                self.ctx.endif_label = outer_endif_label;
 
                 Ok(format!(
                     "; If\n\
                     {condition_asm}\
-                    {SPACE}cmp rax, 0 \n\
+                    {SPACE}cmp {cond_reg}, 0\n\
                     {SPACE}je {false_label}\n\
                     {scope_asm}\
                     {endif_jmp}\
@@ -206,24 +363,48 @@ impl Generator {
                     {endif_goto}"
                 ))
             }
-            NodeStmt::FnSemantics { .. } => {
-                return err!(
-                    self,
+            NodeStmt::FnSemantics { span, .. } => {
+                err!(
+                    span,
                     "Functions cannot be nested, they're top level statements"
                 )
             }
-            NodeStmt::ReturnSemantics { expr } => {
-                todo!("return codegen")
+            NodeStmt::ReturnSemantics { expr, .. } => {
+                let mut expr_asm = String::new();
+                match (expr, self.ctx.return_float) {
+                    (Some(expr), Some(width)) => {
+                        expr_asm = self.gen_float_expr(expr, width)?;
+                        let xmm = self.get_xmm(self.ctx.xmm_count);
+                        self.release_xmm();
+                        if xmm != "xmm0" {
+                            let mov = self.gen_float_mov(width);
+                            expr_asm += &format!("{SPACE}{mov} xmm0, {xmm}\n");
+                        }
+                    }
+                    (Some(expr), None) => expr_asm = self.gen_expr(expr, Some("rax"))?,
+                    (None, _) => (),
+                }
+                Ok(format!(
+                    "; Return\n\
+                     {expr_asm}\
+                     {SPACE}jmp {}\n",
+                    self.ctx.return_label
+                ))
             }
-            NodeStmt::ElseIf { condition, scope } => {
+            NodeStmt::ElseIf {
+                condition, scope, ..
+            } => {
                 let false_label = self.gen_label("ELIF_FALSE");
-                let scope_asm = self.gen_scope(scope)?;
                 let condition_asm = self.gen_expr(condition, None)?;
+                let cond_reg = self.get_reg(self.ctx.reg_count);
+                self.release_reg();
+                let scope_asm = self.gen_scope(scope)?;
                 let endif_label = self.ctx.endif_label.as_str();
 
                 Ok(format!(
-                    "{condition_asm}\n\
-                     {SPACE}cmp rax, 0\n\
+                    "; Else If\n\
+                     {condition_asm}\
+                     {SPACE}cmp {cond_reg}, 0\n\
                      {SPACE}je {false_label}\n\
                      {scope_asm}\
                      {SPACE}jmp {endif_label}\n\
@@ -237,14 +418,27 @@ impl Generator {
                      {scope_asm}"
                 ))
             }
-            NodeStmt::While { condition, scope } => {
+            NodeStmt::While {
+                label,
+                condition,
+                scope,
+                ..
+            } => {
                 let cmp_label = self.gen_label("WHILE_CMP");
                 let scope_label = self.gen_label("WHILE_SCOPE");
                 let loop_end_label = self.gen_label("WHILE_END");
-                self.ctx.loop_end_label = loop_end_label.clone();
 
-                let scope_asm = self.gen_scope(scope)?;
+                self.ctx.loops.push(GenLoop {
+                    label: label.map(|tok| tok.as_str().to_string()),
+                    continue_label: cmp_label.clone(),
+                    end_label: loop_end_label.clone(),
+                });
+                let scope_asm = self.gen_scope(scope);
+                self.ctx.loops.pop();
+                let scope_asm = scope_asm?;
                 let condition_asm = self.gen_expr(condition, None)?;
+                let cond_reg = self.get_reg(self.ctx.reg_count);
+                self.release_reg();
 
                 Ok(format!(
                     "; While\n\
@@ -253,32 +447,194 @@ impl Generator {
                      {scope_asm}\
                      {cmp_label}:\n\
                      {condition_asm}\
-                     {SPACE}cmp rax, 0\n\
+                     {SPACE}cmp {cond_reg}, 0\n\
                      {SPACE}jne {scope_label}\n\
                      {loop_end_label}:\n"
                 ))
             }
-            NodeStmt::Break => Ok(format!(
-                "{SPACE}jmp {label} ; break\n",
-                label = self.ctx.loop_end_label.as_str()
+            NodeStmt::ForSemantics {
+                label,
+                var,
+                range,
+                scope,
+                ..
+            } => {
+                let scope_label = self.gen_label("FOR_SCOPE");
+                let next_label = self.gen_label("FOR_NEXT");
+                let loop_end_label = self.gen_label("FOR_END");
+                let var_count = self.stack.len();
+                let signed = matches!(
+                    self.checker.types.get(var.type_id).map(|t| &t.form),
+                    Some(TypeForm::Base {
+                        type_mode: TypeMode::Int { signed: true }
+                    })
+                );
+
+                // the bounds are evaluated once, 'end' and 'step' are kept in hidden slots
+                // .. named after the loop's label, which no identifier can clash with.
+                let start_asm = self.gen_expr(range.start, None)?;
+                let start_reg = self.get_reg(self.ctx.reg_count);
+                let mut hidden_var = |gen: &mut Self, expr: NodeExpr, name: &str| {
+                    let ident = Token {
+                        kind: TokenKind::Ident,
+                        value: Some(format!("{loop_end_label}_{name}")),
+                        span: expr.span(),
+                        trivia: None,
+                    };
+                    let stk_index = gen.push_var(
+                        ident.clone(),
+                        var.width,
+                        var.type_id,
+                        AddressingMode::Primitive,
+                    );
+                    let stk_pos = gen.gen_stk_access(stk_index, var.width);
+                    let asm = gen.gen_expr(expr, Some(stk_pos.as_str()))?;
+                    Ok::<_, Diagnostic>((ident, asm))
+                };
+                let (end_ident, end_asm) = hidden_var(self, range.end, "end")?;
+                // a literal step is an immediate, defaulting to 1.
+                let (step_ident, step_lit, step_asm) = match range.step {
+                    Some(NodeExpr::Term(NodeTerm::IntLit(tok))) => {
+                        (None, tok.int_lit().0, String::new())
+                    }
+                    Some(expr) => {
+                        let (ident, asm) = hidden_var(self, expr, "step")?;
+                        (Some(ident), 0, asm)
+                    }
+                    None => (None, 1, String::new()),
+                };
+
+                let stk_index = self.push_var(
+                    var.ident.clone(),
+                    var.width,
+                    var.type_id,
+                    AddressingMode::Primitive,
+                );
+                let var_pos = self.gen_stk_access(stk_index, var.width);
+                let start_reg_fit = self.fit_reg(start_reg, &var_pos);
+                self.release_reg();
+
+                let end_var = self.get_var(end_ident.as_str())?.clone();
+                let loop_var = self.get_var(var.ident.as_str())?.clone();
+                let counter_reg = self.next_reg();
+                let end_reg = self.next_reg();
+                let step_reg = self.next_reg();
+                let load_counter = self.gen_var_load(&loop_var, counter_reg);
+                let load_end = self.gen_var_load(&end_var, end_reg);
+                let load_step = match &step_ident {
+                    Some(ident) => self.gen_var_load(self.get_var(ident.as_str())?, step_reg),
+                    None => format!("mov {step_reg}, {step_lit}"),
+                };
+                let counter_reg_fit = self.fit_reg(counter_reg, &var_pos);
+                self.release_reg();
+                self.release_reg();
+                self.release_reg();
+
+                // a literal step was checked, a variable one of 0 would never reach 'end'.
+                // .. checked once the loop is entered, an empty range never looks at its step.
+                let step_guard = match step_ident {
+                    Some(_) => {
+                        let step_ok_label = self.gen_label("FOR_STEP_OK");
+                        let step_jmp = if signed { "jg" } else { "ja" };
+                        format!(
+                            "{SPACE}{load_step}\n\
+                             {SPACE}cmp {step_reg}, 0\n\
+                             {SPACE}{step_jmp} {step_ok_label}\n\
+                             {SPACE}mov rdi, {FOR_STEP_EXIT} ; 'for' step isn't more than 0\n\
+                             {SPACE}mov rax, 60\n\
+                             {SPACE}syscall\n\
+                             {step_ok_label}:\n"
+                        )
+                    }
+                    None => String::new(),
+                };
+
+                // skipped entirely if the range is empty, jumping past 'end' otherwise.
+                let skip_jmp = match (range.inclusive, signed) {
+                    (false, true) => "jge",
+                    (false, false) => "jae",
+                    (true, true) => "jg",
+                    (true, false) => "ja",
+                };
+                // 'end - counter' is never negative, comparing it to the step can't overflow
+                // .. unlike 'counter + step', e.g 'for i: u8 in 0..=255'.
+                let last_jmp = match range.inclusive {
+                    false => "jbe",
+                    true => "jb",
+                };
+
+                // 'continue' skips to the step, not straight back to the body.
+                self.ctx.loops.push(GenLoop {
+                    label: label.map(|tok| tok.as_str().to_string()),
+                    continue_label: next_label.clone(),
+                    end_label: loop_end_label.clone(),
+                });
+                let scope_asm = self.gen_scope(scope);
+                self.ctx.loops.pop();
+                let scope_asm = scope_asm?;
+                self.pop_vars(var_count)?;
+
+                Ok(format!(
+                    "; For\n\
+                     {start_asm}\
+                     {end_asm}\
+                     {step_asm}\
+                     {SPACE}mov {var_pos}, {start_reg_fit} ; Ident('{name}')\n\
+                     {SPACE}{load_counter}\n\
+                     {SPACE}{load_end}\n\
+                     {SPACE}cmp {counter_reg}, {end_reg}\n\
+                     {SPACE}{skip_jmp} {loop_end_label}\n\
+                     {step_guard}\
+                     {scope_label}:\n\
+                     {scope_asm}\
+                     {next_label}:\n\
+                     {SPACE}{load_counter}\n\
+                     {SPACE}{load_end}\n\
+                     {SPACE}{load_step}\n\
+                     {SPACE}sub {end_reg}, {counter_reg}\n\
+                     {SPACE}cmp {end_reg}, {step_reg}\n\
+                     {SPACE}{last_jmp} {loop_end_label}\n\
+                     {SPACE}add {counter_reg}, {step_reg}\n\
+                     {SPACE}mov {var_pos}, {counter_reg_fit}\n\
+                     {SPACE}jmp {scope_label}\n\
+                     {loop_end_label}:\n",
+                    name = var.ident.as_str(),
+                ))
+            }
+            NodeStmt::Break { label, .. } => Ok(format!(
+                "{SPACE}jmp {} ; break\n",
+                self.get_loop(&label)?.end_label
+            )),
+            NodeStmt::Continue { label, .. } => Ok(format!(
+                "{SPACE}jmp {} ; continue\n",
+                self.get_loop(&label)?.continue_label
             )),
-            NodeStmt::VarDecl { .. } | NodeStmt::FnDecl { .. } | NodeStmt::Return { .. } => {
-                err!("Found {stmt:#?}.. shouldn't have.")
+            NodeStmt::VarDecl { .. }
+            | NodeStmt::FnDecl { .. }
+            | NodeStmt::Return { .. }
+            | NodeStmt::For { .. } => {
+                let name = stmt.name();
+                err!("Found '{name}' statement.. shouldn't have.")
             }
         }
     }
 
     // TODO: scope.inherits_stmts does nothing currently.
-    fn gen_scope(&mut self, scope: NodeScope) -> Result<String, String> {
+    fn gen_scope(&mut self, scope: NodeScope) -> Result<String, Diagnostic> {
         debug!("Beginning scope");
 
-        self.ctx.scope_allocations = 0;
         let var_count = self.stack.len();
         let mut asm = String::new();
         for stmt in scope.stmts {
             asm += self.gen_stmt(stmt)?.as_str();
         }
 
+        self.pop_vars(var_count)?;
+        Ok(asm)
+    }
+
+    // removes the variables pushed since the stack held 'var_count'.
+    fn pop_vars(&mut self, var_count: usize) -> Result<(), Diagnostic> {
         let pop_amt = self.stack.len() - var_count;
         debug!("Ending scope, pop({pop_amt})");
         for _ in 0..pop_amt {
@@ -290,27 +646,45 @@ impl Generator {
             self.var_map.remove(popped_var.ident.as_str()).unwrap();
             debug!("Scope ended, removing {popped_var:#?}");
         }
-
-        if self.ctx.scope_allocations == 0 {
-            return Ok(asm);
-        }
-        Ok(format!(
-            "{SPACE}sub rsp, {alloc}\n
-                 {asm}",
-            alloc = self.ctx.scope_allocations
-        ))
+        Ok(())
     }
 
-    fn gen_expr(&mut self, expr: NodeExpr, ans_reg: Option<&str>) -> Result<String, String> {
+    fn gen_expr(&mut self, expr: NodeExpr, ans_reg: Option<&str>) -> Result<String, Diagnostic> {
         debug!(
-            self,
             "{}\ngen expr, reg: {ans_reg:?} \n{expr:#?}\n",
             "-".repeat(20)
         );
+        // floats take their own path, a float variable's width wins over the expression's.
+        if let Some(width) = self.float_width(&expr) {
+            let width = match ans_reg {
+                Some(dest) if dest.starts_with("dword") => 4,
+                Some(dest) if dest.starts_with("qword") => 8,
+                Some(dest) => return err!("Unable to store a float expression in '{dest}'"),
+                None if width == 0 => 8,
+                None => width,
+            };
+            let mut asm = self.gen_float_expr(expr, width)?;
+            if let Some(dest) = ans_reg {
+                let mov = self.gen_float_mov(width);
+                asm += &format!(
+                    "{SPACE}{mov} {dest}, {}\n",
+                    self.get_xmm(self.ctx.xmm_count)
+                );
+                self.release_xmm();
+            }
+            return Ok(asm);
+        }
+
         let mut asm = String::new();
         match expr {
             NodeExpr::Term(term) => return self.gen_term(term, ans_reg),
-            NodeExpr::BinaryExpr { op, lhs, rhs } => {
+            NodeExpr::BinaryExpr { op, lhs, rhs, .. }
+                if op.has_flags(TokenFlags::CMP)
+                    && self.float_width(&lhs).max(self.float_width(&rhs)).is_some() =>
+            {
+                asm += self.gen_float_comparison(op, *lhs, *rhs)?.as_str();
+            }
+            NodeExpr::BinaryExpr { op, lhs, rhs, .. } => {
                 let lhs_asm = self.gen_expr(*lhs, None)?;
                 let rhs_asm = self.gen_expr(*rhs, None)?;
 
@@ -334,31 +708,59 @@ impl Generator {
                 asm += rhs_asm.as_str();
                 asm += op_asm.as_str();
             }
-            NodeExpr::UnaryExpr { op, operand } => {
+            NodeExpr::Cast { expr, type_tok, .. } => {
+                match self.float_width(&expr) {
+                    Some(width) => {
+                        let width = if width == 0 { 8 } else { width };
+                        asm += self.gen_float_expr(*expr, width)?.as_str();
+                        let xmm = self.get_xmm(self.ctx.xmm_count);
+                        self.release_xmm();
+                        let reg = self.next_reg();
+                        let sfx = self.gen_float_suffix(width);
+                        asm += &format!("{SPACE}cvtt{sfx}2si {reg}, {xmm}\n");
+                    }
+                    None => asm += self.gen_expr(*expr, None)?.as_str(),
+                }
+                let reg = self.get_reg(self.ctx.reg_count);
+                asm += self.gen_cast(reg, &type_tok).as_str();
+            }
+            NodeExpr::UnaryExpr { op, operand, .. } => {
                 let operand_clone = *operand.clone();
-                asm += self.gen_expr(*operand, None)?.as_str();
+                match op {
+                    // only the operand's address is needed, a float's value isn't even in 'reg'.
+                    TokenKind::Ampersand => _ = self.next_reg(),
+                    _ => asm += self.gen_expr(*operand, None)?.as_str(),
+                }
 
                 let reg = self.get_reg(self.ctx.reg_count);
                 let op_asm = match op {
                     TokenKind::Tilde => format!("{SPACE}not {reg}\n"),
                     TokenKind::Sub => format!("{SPACE}neg {reg}\n"),
-                    TokenKind::CmpNot => format!(
-                        "{SPACE}test {reg}, {reg}\n\
-                         {SPACE}sete al\n\
-                         {SPACE}movzx {reg}, al\n"
-                    ),
+                    TokenKind::CmpNot => {
+                        let reg_low = self.fit_reg(reg, "byte");
+                        format!(
+                            "{SPACE}test {reg}, {reg}\n\
+                             {SPACE}sete {reg_low}\n\
+                             {SPACE}movzx {reg}, {reg_low}\n"
+                        )
+                    }
                     TokenKind::Ampersand => match operand_clone {
                         NodeExpr::Term(NodeTerm::Ident(name)) => {
                             let stk_pos = self.get_var(name.as_str())?.stk_index;
-                            format!("{SPACE}lea {reg}, [rbp+{stk_pos}]\n")
+                            format!("{SPACE}lea {reg}, [rbp-{stk_pos}]\n")
                         }
                         _ => return err!("Attempted 'addr_of' operation, found right hand value"),
                     },
-                    TokenKind::Ptr => {
-                        // TODO(TOM): hardcoded 8 byte ptr size. doesn't work proper..
-                        // format!("{SPACE}mov {reg}, {} [{reg}]\n", self.gen_access_size(8))
-                        format!("{SPACE}mov {reg}, [{reg}]\n")
-                    }
+                    // a pointer variable knows the width it points to.
+                    TokenKind::Ptr => match operand_clone {
+                        NodeExpr::Term(NodeTerm::Ident(name)) => {
+                            let type_id = self.get_var(name.as_str())?.type_id;
+                            let width = self.checker.types.get(type_id).unwrap().width;
+                            let src = format!("{} [{reg}]", self.gen_access_size(width));
+                            format!("{SPACE}{}\n", self.gen_load(reg, &src, width, type_id))
+                        }
+                        _ => format!("{SPACE}mov {reg}, [{reg}]\n"),
+                    },
                     _ => return err!("Unable to generate unary expression: '{op:?}'"),
                 };
                 asm += op_asm.as_str();
@@ -367,58 +769,379 @@ impl Generator {
         // don't need to release reg if its just operation, just doing stuff on data.
         // only release if changing stack data.
         if let Some(reg) = ans_reg {
-            // this is an assign, allocate space for it ?
-            asm += format!("{SPACE}mov {reg}, {}\n", self.get_reg(self.ctx.reg_count)).as_str();
+            let ans = self.get_reg(self.ctx.reg_count);
+            let src = self.fit_reg(ans, reg);
+            asm += format!("{SPACE}mov {reg}, {src}\n").as_str();
             self.release_reg();
         }
         Ok(asm)
     }
 
-    fn gen_term(&mut self, term: NodeTerm, ans_reg: Option<&str>) -> Result<String, String> {
+    fn gen_term(&mut self, term: NodeTerm, ans_reg: Option<&str>) -> Result<String, Diagnostic> {
         match term {
-            NodeTerm::False | NodeTerm::True => todo!("NodeTerm False/True"),
-            NodeTerm::IntLit(tok) => {
-                self.pos = tok.pos;
+            NodeTerm::True(_) | NodeTerm::False(_) => {
+                let val = if matches!(term, NodeTerm::True(_)) {
+                    1
+                } else {
+                    0
+                };
                 let reg = match ans_reg {
                     Some(reg) => reg,
                     None => self.next_reg(),
                 };
-                Ok(format!("{SPACE}mov {reg}, {}\n", tok.as_str()))
+                Ok(format!("{SPACE}mov {reg}, {val}\n"))
             }
-            NodeTerm::Ident(tok) => {
-                self.pos = tok.pos;
-                let var = self.get_var(tok.as_str())?;
-                let stk_pos = self.gen_var_access(var.stk_index, var.width);
+            NodeTerm::IntLit(tok) => {
+                let value = tok.int_lit().0;
+                match ans_reg {
+                    // memory only takes a sign extended 32 bit immediate, wider goes through a register.
+                    Some(dest) if dest.contains('[') && value > i32::MAX as u64 => {
+                        let reg = self.next_reg();
+                        let src = self.fit_reg(reg, dest);
+                        self.release_reg();
+                        Ok(format!(
+                            "{SPACE}mov {reg}, {value}\n\
+                             {SPACE}mov {dest}, {src}\n"
+                        ))
+                    }
+                    Some(reg) => Ok(format!("{SPACE}mov {reg}, {value}\n")),
+                    None => {
+                        let reg = self.next_reg();
+                        Ok(format!("{SPACE}mov {reg}, {value}\n"))
+                    }
+                }
+            }
+            NodeTerm::FloatLit(tok) => {
+                err!(tok.span, "Float literal reached integer code generation")
+            }
+            NodeTerm::CharLit(tok) => {
                 let reg = match ans_reg {
                     Some(reg) => reg,
                     None => self.next_reg(),
                 };
-                Ok(format!("{SPACE}mov {reg}, {stk_pos} ; {tok:?}\n"))
+                let code = tok.as_str().chars().next().unwrap() as u32;
+                Ok(format!("{SPACE}mov {reg}, {code}\n"))
+            }
+            NodeTerm::Ident(tok) => {
+                let var = self.get_var(tok.as_str())?.clone();
+                match ans_reg {
+                    // memory to memory moves don't exist, go through a register.
+                    Some(dest) if dest.contains('[') => {
+                        let reg = self.next_reg();
+                        let load = self.gen_var_load(&var, reg);
+                        let src = self.fit_reg(reg, dest);
+                        self.release_reg();
+                        Ok(format!(
+                            "{SPACE}{load} ; {tok:?}\n\
+                             {SPACE}mov {dest}, {src}\n"
+                        ))
+                    }
+                    Some(reg) => Ok(format!(
+                        "{SPACE}{} ; {tok:?}\n",
+                        self.gen_var_load(&var, reg)
+                    )),
+                    None => {
+                        let reg = self.next_reg();
+                        Ok(format!(
+                            "{SPACE}{} ; {tok:?}\n",
+                            self.gen_var_load(&var, reg)
+                        ))
+                    }
+                }
+            }
+            NodeTerm::FnCall {
+                args,
+                span,
+                signature,
+                ..
+            } => {
+                let mut asm = self.gen_fn_call(args, signature, span)?;
+                if let Some(dest) = ans_reg {
+                    let reg = self.get_reg(self.ctx.reg_count);
+                    let src = self.fit_reg(reg, dest);
+                    asm += &format!("{SPACE}mov {dest}, {src}\n");
+                    self.release_reg();
+                }
+                Ok(asm)
+            }
+        }
+    }
+
+    // The result is left in the next register, or the next 'xmm' register for a float.
+    fn gen_fn_call(
+        &mut self,
+        args: Vec<NodeExpr>,
+        signature: Option<String>,
+        span: Span,
+    ) -> Result<String, Diagnostic> {
+        let func = match signature {
+            Some(signature) => self.checker.fn_map.get(&signature).unwrap().clone(),
+            None => return err!(span, "Function call was never checked"),
+        };
+        let label = self.gen_fn_label(&func.signature);
+        let (live_regs, live_xmms) = (self.ctx.reg_count, self.ctx.xmm_count);
+
+        let mut asm = String::new();
+        for index in 1..=live_regs {
+            asm += &format!("{SPACE}push {}\n", self.get_reg(index));
+        }
+        for index in 1..=live_xmms {
+            let xmm = self.get_xmm(index);
+            asm += &format!("{SPACE}sub rsp, 8\n{SPACE}movq qword [rsp], {xmm}\n");
+        }
+
+        // every arg is on the stack before any is moved in, evaluating one could clobber another.
+        let mut pops = Vec::with_capacity(args.len());
+        let (mut int_args, mut float_args) = (0, 0);
+        for (arg, param) in args.into_iter().zip(&func.arg_semantics) {
+            let float_width = match param.addr_mode {
+                AddressingMode::Primitive => self.float_type(param.type_id),
+                _ => None,
+            };
+            match float_width {
+                Some(width) if float_args < 8 => {
+                    asm += &self.gen_float_expr(arg, width)?;
+                    let xmm = self.get_xmm(self.ctx.xmm_count);
+                    self.release_xmm();
+                    let mov = self.gen_float_mov(width);
+                    let size = self.gen_access_size(width);
+                    asm += &format!("{SPACE}sub rsp, 8\n{SPACE}{mov} {size} [rsp], {xmm}\n");
+                    pops.push(format!(
+                        "{SPACE}{mov} xmm{float_args}, {size} [rsp]\n{SPACE}add rsp, 8\n"
+                    ));
+                    float_args += 1;
+                }
+                None if int_args < ARG_REGISTERS.len() => {
+                    asm += &self.gen_expr(arg, None)?;
+                    asm += &format!("{SPACE}push {}\n", self.get_reg(self.ctx.reg_count));
+                    self.release_reg();
+                    pops.push(format!("{SPACE}pop {}\n", ARG_REGISTERS[int_args]));
+                    int_args += 1;
+                }
+                _ => {
+                    let signature = &func.signature;
+                    return err!(
+                        span,
+                        "Too many arguments to pass in registers in '{signature}'"
+                    );
+                }
+            }
+        }
+        for pop in pops.iter().rev() {
+            asm += pop;
+        }
+        asm += &format!("{SPACE}call {label}\n");
+
+        // the result register was free before the call, restoring the others can't clobber it.
+        match self.return_float(&func) {
+            Some(width) => {
+                let xmm = self.next_xmm();
+                if xmm != "xmm0" {
+                    let mov = self.gen_float_mov(width);
+                    asm += &format!("{SPACE}{mov} {xmm}, xmm0\n");
+                }
+            }
+            None => {
+                let reg = self.next_reg();
+                if reg != "rax" {
+                    asm += &format!("{SPACE}mov {reg}, rax\n");
+                }
+            }
+        }
+        for index in (1..=live_xmms).rev() {
+            let xmm = self.get_xmm(index);
+            asm += &format!("{SPACE}movq {xmm}, qword [rsp]\n{SPACE}add rsp, 8\n");
+        }
+        for index in (1..=live_regs).rev() {
+            asm += &format!("{SPACE}pop {}\n", self.get_reg(index));
+        }
+        Ok(asm)
+    }
+
+    // The result is left in the next 'xmm' register, as a float of 'width' bytes.
+    // .. every value is converted to 'width' as it's loaded, e.g an 'f32' in an 'f64' expression.
+    fn gen_float_expr(&mut self, expr: NodeExpr, width: Byte) -> Result<String, Diagnostic> {
+        let expr_width = self.float_width(&expr).unwrap_or(width);
+        let sfx = self.gen_float_suffix(width);
+        match expr {
+            NodeExpr::Term(NodeTerm::FloatLit(tok)) => {
+                let xmm = self.next_xmm();
+                let reg = self.next_reg();
+                self.release_reg();
+                let value = tok.float_lit().0;
+                Ok(match width {
+                    4 => format!(
+                        "{SPACE}mov {reg32}, {bits} ; {value:?}\n\
+                         {SPACE}movd {xmm}, {reg32}\n",
+                        reg32 = self.fit_reg(reg, "dword"),
+                        bits = (value as f32).to_bits(),
+                    ),
+                    _ => format!(
+                        "{SPACE}mov {reg}, {bits} ; {value:?}\n\
+                         {SPACE}movq {xmm}, {reg}\n",
+                        bits = value.to_bits(),
+                    ),
+                })
+            }
+            NodeExpr::Term(NodeTerm::FnCall {
+                args,
+                span,
+                signature,
+                ..
+            }) => {
+                let mut asm = self.gen_fn_call(args, signature, span)?;
+                let xmm = self.get_xmm(self.ctx.xmm_count);
+                asm += &self.gen_float_convert(xmm, expr_width, width);
+                Ok(asm)
+            }
+            NodeExpr::Term(NodeTerm::Ident(tok)) => {
+                let var = self.get_var(tok.as_str())?.clone();
+                let xmm = self.next_xmm();
+                let mov = self.gen_float_mov(var.width);
+                let src = self.gen_stk_access(var.stk_index, var.width);
+                Ok(format!(
+                    "{SPACE}{mov} {xmm}, {src} ; {tok:?}\n{}",
+                    self.gen_float_convert(xmm, var.width, width)
+                ))
+            }
+            NodeExpr::BinaryExpr { op, lhs, rhs, .. } => {
+                let mut asm = self.gen_float_expr(*lhs, width)?;
+                asm += self.gen_float_expr(*rhs, width)?.as_str();
+                let xmm1 = self.get_xmm(self.ctx.xmm_count - 1);
+                let xmm2 = self.get_xmm(self.ctx.xmm_count);
+                self.release_xmm();
+                let op_asm = self.gen_float_op(op)?;
+                asm += &format!("{SPACE}{op_asm}{sfx} {xmm1}, {xmm2}\n");
+                Ok(asm)
+            }
+            NodeExpr::UnaryExpr {
+                op: TokenKind::Sub,
+                operand,
+                ..
+            } => {
+                // flip the sign bit through a general purpose register, '0.0 - x' loses '-0.0'.
+                let mut asm = self.gen_float_expr(*operand, width)?;
+                let xmm = self.get_xmm(self.ctx.xmm_count);
+                let reg = self.next_reg();
+                self.release_reg();
+                asm += &match width {
+                    4 => {
+                        let reg32 = self.fit_reg(reg, "dword");
+                        format!(
+                            "{SPACE}movd {reg32}, {xmm}\n\
+                             {SPACE}btc {reg32}, 31\n\
+                             {SPACE}movd {xmm}, {reg32}\n"
+                        )
+                    }
+                    _ => format!(
+                        "{SPACE}movq {reg}, {xmm}\n\
+                         {SPACE}btc {reg}, 63\n\
+                         {SPACE}movq {xmm}, {reg}\n"
+                    ),
+                };
+                Ok(asm)
+            }
+            NodeExpr::UnaryExpr {
+                op: TokenKind::Ptr,
+                operand,
+                ..
+            } => {
+                let mut asm = self.gen_expr(*operand, None)?;
+                let reg = self.get_reg(self.ctx.reg_count);
+                self.release_reg();
+                let xmm = self.next_xmm();
+                let mov = self.gen_float_mov(expr_width);
+                let size = self.gen_access_size(expr_width);
+                asm += &format!(
+                    "{SPACE}{mov} {xmm}, {size} [{reg}]\n{}",
+                    self.gen_float_convert(xmm, expr_width, width)
+                );
+                Ok(asm)
             }
-            NodeTerm::FnCall { ident, args } => todo!(),
+            NodeExpr::Cast { expr, .. } => {
+                let mut asm = match self.float_width(&expr) {
+                    Some(_) => self.gen_float_expr(*expr, expr_width)?,
+                    None => {
+                        let mut asm = self.gen_expr(*expr, None)?;
+                        let reg = self.get_reg(self.ctx.reg_count);
+                        self.release_reg();
+                        let xmm = self.next_xmm();
+                        let cast_sfx = self.gen_float_suffix(expr_width);
+                        asm += &format!("{SPACE}cvtsi2{cast_sfx} {xmm}, {reg}\n");
+                        asm
+                    }
+                };
+                let xmm = self.get_xmm(self.ctx.xmm_count);
+                asm += &self.gen_float_convert(xmm, expr_width, width);
+                Ok(asm)
+            }
+            _ => err!("Unable to generate float expression:\n{expr:#?}"),
         }
     }
 
+    // 'cmpXXsd' fills its first operand with ones when true, only 'NotEq' is true for a NaN.
+    // .. '>' && '>=' are '<' && '<=' with the operands swapped.
+    fn gen_float_comparison(
+        &mut self,
+        op: TokenKind,
+        lhs: NodeExpr,
+        rhs: NodeExpr,
+    ) -> Result<String, Diagnostic> {
+        let width = match self.float_width(&lhs).max(self.float_width(&rhs)) {
+            Some(0) | None => 8,
+            Some(width) => width,
+        };
+        let mut asm = self.gen_float_expr(lhs, width)?;
+        asm += self.gen_float_expr(rhs, width)?.as_str();
+        let xmm1 = self.get_xmm(self.ctx.xmm_count - 1);
+        let xmm2 = self.get_xmm(self.ctx.xmm_count);
+        self.release_xmm();
+        self.release_xmm();
+
+        let (predicate, dest, src) = match op {
+            TokenKind::CmpEq => ("eq", xmm1, xmm2),
+            TokenKind::NotEq => ("neq", xmm1, xmm2),
+            TokenKind::Lt => ("lt", xmm1, xmm2),
+            TokenKind::LtEq => ("le", xmm1, xmm2),
+            TokenKind::Gt => ("lt", xmm2, xmm1),
+            TokenKind::GtEq => ("le", xmm2, xmm1),
+            _ => return err!("Unable to generate float comparison '{op:?}'"),
+        };
+        let sfx = self.gen_float_suffix(width);
+        let reg = self.next_reg();
+        let mov_mask = match width {
+            4 => format!("movd {}, {dest}", self.fit_reg(reg, "dword")),
+            _ => format!("movq {reg}, {dest}"),
+        };
+        asm += &format!(
+            "{SPACE}cmp{predicate}{sfx} {dest}, {src}\n\
+             {SPACE}{mov_mask}\n\
+             {SPACE}and {reg}, 1\n"
+        );
+        Ok(asm)
+    }
+
     // TODO: Remove excess 'cmp', do 'Constant Folding'
-    // "movzx {reg1},al" << zeros reg && moves in al (0,1).
+    // lhs is in reg1, rhs in reg2. the answer is left in reg1.
     fn gen_logical(
         &mut self,
         op: TokenKind,
         ans_reg: Option<&str>,
         lhs_asm: String,
         rhs_asm: String,
-    ) -> Result<String, String> {
+    ) -> Result<String, Diagnostic> {
         let reg1 = self.get_reg(self.ctx.reg_count - 1);
         let reg2 = self.get_reg(self.ctx.reg_count);
+        self.release_reg();
         let mut mov_ans = String::new();
         if let Some(reg) = ans_reg {
-            mov_ans = format!("{SPACE}mov {reg}, {}\n", self.get_reg(self.ctx.reg_count));
+            mov_ans = format!("{SPACE}mov {reg}, {}\n", self.fit_reg(reg1, reg));
             self.release_reg();
         }
         match op {
             TokenKind::CmpAnd => {
                 let false_label = self.gen_label("AND_FALSE");
-                let true_label = self.gen_label("AND_TRUE");
+                let end_label = self.gen_label("AND_END");
 
                 Ok(format!(
                     "; LogicalAnd\n\
@@ -427,20 +1150,18 @@ impl Generator {
                     {SPACE}je {false_label}\n\
                     {rhs_asm}\
                     {SPACE}cmp {reg2}, 0\n\
-                    {SPACE}je {true_label}\n\
+                    {SPACE}je {false_label}\n\
                     {SPACE}mov {reg1}, 1\n\
-                    {SPACE}jmp {true_label}\n\
+                    {SPACE}jmp {end_label}\n\
                     {false_label}:\n\
                     {SPACE}mov {reg1}, 0\n\
-                    {true_label}:\n\
-                    {SPACE}movzx {reg1}, al\n\
+                    {end_label}:\n\
                     {mov_ans}"
                 ))
             }
             TokenKind::CmpOr => {
-                let false_label = self.gen_label("OR_FALSE");
                 let true_label = self.gen_label("OR_TRUE");
-                let final_label = self.gen_label("OR_FINAL");
+                let end_label = self.gen_label("OR_END");
 
                 Ok(format!(
                     "; CmpOr\n\
@@ -449,14 +1170,12 @@ impl Generator {
                     {SPACE}jne {true_label}\n\
                     {rhs_asm}\
                     {SPACE}cmp {reg2}, 0\n\
-                    {SPACE}je {false_label}\n\
+                    {SPACE}jne {true_label}\n\
+                    {SPACE}mov {reg1}, 0\n\
+                    {SPACE}jmp {end_label}\n\
                     {true_label}:\n\
                     {SPACE}mov {reg1}, 1\n\
-                    {SPACE}jmp {final_label}\n\
-                    {false_label}:\n\
-                    {SPACE}mov {reg1}, 0\n\
-                    {final_label}:\n\
-                    {SPACE}movzx {reg1}, al\n\
+                    {end_label}:\n\
                     {mov_ans}"
                 ))
             }
@@ -464,21 +1183,35 @@ impl Generator {
         }
     }
 
-    fn gen_arithmetic(&mut self, op: TokenKind) -> Result<String, String> {
+    fn gen_arithmetic(&mut self, op: TokenKind) -> Result<String, Diagnostic> {
         let reg1 = self.get_reg(self.ctx.reg_count - 1); // first value is further down because its a stack
         let reg2 = self.get_reg(self.ctx.reg_count);
         let operation_asm = match op {
             TokenKind::Add => format!("add {reg1}, {reg2}"),
             TokenKind::Sub => format!("sub {reg1}, {reg2}"),
             TokenKind::Mul => format!("imul {reg1}, {reg2}"),
-            TokenKind::Quo => format!("cqo\n{SPACE}idiv {reg2}"),
-            TokenKind::Mod => format!("cqo\n{SPACE}idiv {reg2}\n{SPACE}mov {reg1}, rdx"), // TODO: 'cqo', instruction changes with reg size
+            TokenKind::Quo | TokenKind::Mod => {
+                // idiv only divides rax (rdx:rax), save it if it holds another value.
+                let ans = if op == TokenKind::Quo { "rax" } else { "rdx" };
+                let div_asm = format!("cqo\n{SPACE}idiv {reg2}");
+                return Ok(match reg1 {
+                    "rax" if op == TokenKind::Quo => format!("{SPACE}{div_asm}\n"),
+                    "rax" => format!("{SPACE}{div_asm}\n{SPACE}mov rax, {ans}\n"),
+                    _ => format!(
+                        "{SPACE}push rax\n\
+                         {SPACE}mov rax, {reg1}\n\
+                         {SPACE}{div_asm}\n\
+                         {SPACE}mov {reg1}, {ans}\n\
+                         {SPACE}pop rax\n"
+                    ),
+                });
+            }
             _ => return err!("Unable to generate Arithmetic operation: '{op:?}'"),
         };
         Ok(format!("{SPACE}{operation_asm}\n"))
     }
 
-    fn gen_bitwise(&mut self, op: TokenKind) -> Result<String, String> {
+    fn gen_bitwise(&mut self, op: TokenKind) -> Result<String, Diagnostic> {
         let reg1 = self.get_reg(self.ctx.reg_count - 1);
         let reg2 = self.get_reg(self.ctx.reg_count);
         let asm = match op {
@@ -494,21 +1227,22 @@ impl Generator {
         Ok(format!("{SPACE}{asm} {reg1}, {reg2}\n"))
     }
 
-    fn gen_comparison(&mut self, op: TokenKind) -> Result<String, String> {
+    fn gen_comparison(&mut self, op: TokenKind) -> Result<String, Diagnostic> {
         let reg1 = self.get_reg(self.ctx.reg_count - 1);
         let reg2 = self.get_reg(self.ctx.reg_count);
 
         let cmp_mod = self.gen_cmp_modifier(op)?;
         let set_asm = format!("set{}", cmp_mod);
+        let reg1_low = self.fit_reg(reg1, "byte");
 
         Ok(format!(
             "{SPACE}cmp {reg1}, {reg2}\n\
-             {SPACE}{set_asm} al\n\
-             {SPACE}movzx {reg1}, al\n"
+             {SPACE}{set_asm} {reg1_low}\n\
+             {SPACE}movzx {reg1}, {reg1_low}\n"
         ))
     }
 
-    fn gen_cmp_modifier(&mut self, op: TokenKind) -> Result<&str, String> {
+    fn gen_cmp_modifier(&mut self, op: TokenKind) -> Result<&str, Diagnostic> {
         match op {
             TokenKind::CmpEq => Ok("e"),
             TokenKind::NotEq => Ok("ne"),
@@ -525,16 +1259,275 @@ impl Generator {
         format!(".{:X}_{name}", self.ctx.label_count) // '.' denotes a local scoped label in asm
     }
 
-    // TODO(TOM): need to use word_size??
-    fn gen_var_access(&self, stk_index: usize, word_size: Byte) -> String {
-        format!("[rbp-{stk_index}]")
+    // '.' denotes a local label, a signature isn't a valid label either, e.g 'plus5(i32,i32)'
+    fn gen_fn_label(&self, signature: &str) -> String {
+        match signature {
+            "main" => "main".to_string(),
+            _ => signature.trim_end_matches(')').replace(['(', ','], "_"),
+        }
     }
 
-    fn gen_stk_access(&mut self, stk_index: usize, word_size: Byte) -> String {
-        self.ctx.scope_allocations += word_size;
+    // stores 'expr' in an assignment's target, a variable or through a pointer variable.
+    fn gen_assign(&mut self, target: NodeExpr, expr: NodeExpr) -> Result<String, Diagnostic> {
+        match target {
+            NodeExpr::Term(NodeTerm::Ident(ident)) => {
+                let var = self.get_var(ident.as_str())?;
+                let dest = self.gen_stk_access(var.stk_index, var.width);
+                self.gen_expr(expr, Some(dest.as_str()))
+            }
+            NodeExpr::UnaryExpr {
+                op: TokenKind::Ptr,
+                operand,
+                ..
+            } => {
+                let NodeExpr::Term(NodeTerm::Ident(ident)) = *operand else {
+                    return err!(operand.span(), "Unable to assign through '{operand:?}'");
+                };
+                let var = self.get_var(ident.as_str())?.clone();
+                let width = self.checker.types.get(var.type_id).unwrap().width;
+
+                // the address is held first, the expression only uses the registers after it.
+                let addr_reg = self.next_reg();
+                let ptr = self.gen_stk_access(var.stk_index, var.width);
+                let dest = format!("{} [{addr_reg}]", self.gen_access_size(width));
+                let expr_asm = self.gen_expr(expr, Some(dest.as_str()))?;
+                self.release_reg();
+                Ok(format!("{SPACE}mov {addr_reg}, {ptr}\n{expr_asm}"))
+            }
+            _ => err!(target.span(), "Unable to assign to '{target:?}'"),
+        }
+    }
+
+    // reads and writes back through the same address register, 'operand' is only loaded once.
+    fn gen_ptr_compound_assign(
+        &mut self,
+        operand: NodeExpr,
+        op: TokenKind,
+        expr: NodeExpr,
+    ) -> Result<String, Diagnostic> {
+        let NodeExpr::Term(NodeTerm::Ident(ident)) = operand else {
+            return err!(operand.span(), "Unable to assign through '{operand:?}'");
+        };
+        let var = self.get_var(ident.as_str())?.clone();
+        let width = self.checker.types.get(var.type_id).unwrap().width;
+
+        let addr_reg = self.next_reg();
+        let ptr = self.gen_stk_access(var.stk_index, var.width);
+        let dest = format!("{} [{addr_reg}]", self.gen_access_size(width));
+        let mut asm = format!("{SPACE}mov {addr_reg}, {ptr}\n");
+
+        if let Some(width) = self.float_type(var.type_id) {
+            let xmm = self.next_xmm();
+            let mov = self.gen_float_mov(width);
+            asm += &format!("{SPACE}{mov} {xmm}, {dest}\n");
+            asm += &self.gen_float_expr(expr, width)?;
+            let rhs_xmm = self.get_xmm(self.ctx.xmm_count);
+            self.release_xmm();
+            let op_asm = self.gen_float_op(op)?;
+            let sfx = self.gen_float_suffix(width);
+            asm += &format!(
+                "{SPACE}{op_asm}{sfx} {xmm}, {rhs_xmm}\n\
+                 {SPACE}{mov} {dest}, {xmm}\n"
+            );
+            self.release_xmm();
+        } else {
+            let lhs_reg = self.next_reg();
+            asm += &format!(
+                "{SPACE}{}\n",
+                self.gen_load(lhs_reg, &dest, width, var.type_id)
+            );
+            asm += &self.gen_expr(expr, None)?;
+            asm += &match op.has_flags(TokenFlags::BIT) {
+                true => self.gen_bitwise(op)?,
+                false => self.gen_arithmetic(op)?,
+            };
+            self.release_reg();
+            asm += &format!("{SPACE}mov {dest}, {}\n", self.fit_reg(lhs_reg, &dest));
+            self.release_reg();
+        }
+        self.release_reg();
+        Ok(asm)
+    }
+
+    // loads a variable into a 64 bit register, extended by its sign.
+    fn gen_var_load(&self, var: &GenVariable, reg: &str) -> String {
+        let src = self.gen_stk_access(var.stk_index, var.width);
+        self.gen_load(reg, &src, var.width, var.type_id)
+    }
+
+    // loads 'width' bytes at 'src' into a 64 bit register, extended by the type's sign.
+    fn gen_load(&self, reg: &str, src: &str, width: Byte, type_id: usize) -> String {
+        let signed = matches!(
+            self.checker.types.get(type_id).map(|t| &t.form),
+            Some(TypeForm::Base {
+                type_mode: TypeMode::Int { signed: true }
+            })
+        );
+        match width {
+            8 => format!("mov {reg}, {src}"),
+            4 if signed => format!("movsxd {reg}, {src}"),
+            4 => format!("mov {}, {src}", self.fit_reg(reg, "dword")),
+            _ if signed => format!("movsx {reg}, {src}"),
+            _ => format!("movzx {reg}, {src}"),
+        }
+    }
+
+    // truncates to the cast type's width, then extends back to 64 bits by its sign.
+    fn gen_cast(&self, reg: &str, type_tok: &Token) -> String {
+        let type_id = *self.checker.type_map.get(type_tok.as_str()).unwrap();
+        let cast_type = self.checker.types.get(type_id).unwrap();
+        let signed = matches!(
+            cast_type.form,
+            TypeForm::Base {
+                type_mode: TypeMode::Int { signed: true }
+            }
+        );
+        let sub_reg = self.fit_reg(reg, self.gen_access_size(cast_type.width));
+        match cast_type.width {
+            8 => String::new(),
+            4 if signed => format!("{SPACE}movsxd {reg}, {sub_reg}\n"),
+            4 => format!("{SPACE}mov {sub_reg}, {sub_reg}\n"),
+            _ if signed => format!("{SPACE}movsx {reg}, {sub_reg}\n"),
+            _ => format!("{SPACE}movzx {reg}, {sub_reg}\n"),
+        }
+    }
+
+    // 'Some(width)' if 'expr' is a float, 'Some(0)' when it's only made of unsuffixed literals.
+    // .. comparisons are booleans, only their operands are floats.
+    fn float_width(&self, expr: &NodeExpr) -> Option<Byte> {
+        match expr {
+            NodeExpr::Term(NodeTerm::FloatLit(tok)) => match tok.float_lit().1 {
+                Some("f32") => Some(4),
+                Some(_) => Some(8),
+                None => Some(0),
+            },
+            NodeExpr::Term(NodeTerm::Ident(tok)) => match self.get_var(tok.as_str()) {
+                Ok(var) if var.addr_mode == AddressingMode::Primitive => {
+                    self.float_type(var.type_id)
+                }
+                _ => None,
+            },
+            NodeExpr::BinaryExpr { op, lhs, rhs, .. } if op.has_flags(TokenFlags::ARITH) => {
+                self.float_width(lhs).max(self.float_width(rhs))
+            }
+            NodeExpr::UnaryExpr {
+                op: TokenKind::Sub,
+                operand,
+                ..
+            } => self.float_width(operand),
+            NodeExpr::UnaryExpr {
+                op: TokenKind::Ptr,
+                operand,
+                ..
+            } => match operand.as_ref() {
+                NodeExpr::Term(NodeTerm::Ident(tok)) => match self.get_var(tok.as_str()) {
+                    Ok(var) if var.addr_mode == AddressingMode::Pointer => {
+                        self.float_type(var.type_id)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            NodeExpr::Cast { type_tok, .. } => {
+                self.float_type(*self.checker.type_map.get(type_tok.as_str())?)
+            }
+            NodeExpr::Term(NodeTerm::FnCall { signature, .. }) => {
+                self.return_float(self.checker.fn_map.get(signature.as_ref()?)?)
+            }
+            _ => None,
+        }
+    }
+
+    fn return_float(&self, func: &SemFn) -> Option<Byte> {
+        match func.return_type_data {
+            Some(ExprData {
+                type_mode: TypeMode::Float { .. },
+                addr_mode: AddressingMode::Primitive,
+                form: ExprForm::Expr { inherited_width },
+            }) => Some(inherited_width),
+            _ => None,
+        }
+    }
+
+    fn float_type(&self, type_id: usize) -> Option<Byte> {
+        match self.checker.types.get(type_id) {
+            Some(Type {
+                width,
+                form:
+                    TypeForm::Base {
+                        type_mode: TypeMode::Float { .. },
+                    },
+                ..
+            }) => Some(*width),
+            _ => None,
+        }
+    }
+
+    fn gen_float_convert(&self, xmm: &str, from: Byte, to: Byte) -> String {
+        match (from, to) {
+            (4, 8) => format!("{SPACE}cvtss2sd {xmm}, {xmm}\n"),
+            (8, 4) => format!("{SPACE}cvtsd2ss {xmm}, {xmm}\n"),
+            _ => String::new(),
+        }
+    }
+
+    // 'ss' scalar single (f32), 'sd' scalar double (f64)
+    fn gen_float_op(&self, op: TokenKind) -> Result<&'static str, Diagnostic> {
+        Ok(match op {
+            TokenKind::Add => "add",
+            TokenKind::Sub => "sub",
+            TokenKind::Mul => "mul",
+            TokenKind::Quo => "div",
+            _ => return err!("Unable to generate float operation: '{op:?}'"),
+        })
+    }
+
+    fn gen_float_suffix(&self, width: Byte) -> &'static str {
+        match width {
+            4 => "ss",
+            _ => "sd",
+        }
+    }
+
+    fn gen_float_mov(&self, width: Byte) -> String {
+        format!("mov{}", self.gen_float_suffix(width))
+    }
+
+    fn gen_stk_access(&self, stk_index: usize, word_size: Byte) -> String {
         format!("{} [rbp-{stk_index}]", self.gen_access_size(word_size))
     }
 
+    // 'sub rsp' to reserve the frame, a multiple of 16 to keep the stack aligned.
+    fn gen_frame_alloc(&self) -> String {
+        match self.ctx.frame_size {
+            0 => String::new(),
+            size => format!("{SPACE}sub rsp, {}\n", size.div_ceil(16) * 16),
+        }
+    }
+
+    // sub-register of a 64 bit register, sized to fit 'dest', e.g (rax, "byte [rbp-1]") => al
+    fn fit_reg<'a>(&self, reg: &'a str, dest: &str) -> &'a str {
+        let size = match dest.split(' ').next() {
+            Some("byte") => 0,
+            Some("word") => 1,
+            Some("dword") => 2,
+            _ => return reg,
+        };
+        let sub_regs = match reg {
+            "rax" => ["al", "ax", "eax"],
+            "rcx" => ["cl", "cx", "ecx"],
+            "rdx" => ["dl", "dx", "edx"],
+            "rsi" => ["sil", "si", "esi"],
+            "rdi" => ["dil", "di", "edi"],
+            "r8" => ["r8b", "r8w", "r8d"],
+            "r9" => ["r9b", "r9w", "r9d"],
+            "r10" => ["r10b", "r10w", "r10d"],
+            "r11" => ["r11b", "r11w", "r11d"],
+            _ => return reg,
+        };
+        sub_regs[size]
+    }
+
     fn gen_access_size(&self, word_size: Byte) -> &str {
         match word_size {
             1 => "byte",
@@ -570,7 +1563,65 @@ impl Generator {
         self.ctx.reg_count -= 1;
     }
 
-    fn get_var(&self, ident: &str) -> Result<&GenVariable, String> {
+    fn next_xmm(&mut self) -> &'static str {
+        match XMM_REGISTERS.get(self.ctx.xmm_count) {
+            Some(xmm) => {
+                self.ctx.xmm_count += 1;
+                xmm
+            }
+            None => panic!("out of xmm registers!"),
+        }
+    }
+
+    fn get_xmm(&self, index: usize) -> &'static str {
+        XMM_REGISTERS[index - 1]
+    }
+
+    fn release_xmm(&mut self) {
+        self.ctx.xmm_count -= 1;
+    }
+
+    // places a variable on top of the stack, returns its 'stk_index'
+    fn push_var(
+        &mut self,
+        ident: Token,
+        width: Byte,
+        type_id: usize,
+        addr_mode: AddressingMode,
+    ) -> Byte {
+        self.stk_pos += width;
+        self.ctx.frame_size = self.ctx.frame_size.max(self.stk_pos);
+        let var = GenVariable {
+            ident,
+            stk_index: self.stk_pos,
+            type_id,
+            addr_mode,
+            width,
+        };
+        self.var_map
+            .insert(var.ident.as_str().to_string(), self.stack.len());
+        self.stack.push(var);
+        self.stk_pos
+    }
+
+    // the loop a 'break' or 'continue' jumps out of, the innermost without a label.
+    fn get_loop(&self, label: &Option<Token>) -> Result<&GenLoop, Diagnostic> {
+        let found = match label {
+            Some(label) => self
+                .ctx
+                .loops
+                .iter()
+                .rev()
+                .find(|l| l.label.as_deref() == Some(label.as_str())),
+            None => self.ctx.loops.last(),
+        };
+        match found {
+            Some(gen_loop) => Ok(gen_loop),
+            None => err!("Loop: {label:?} doesn't exist."),
+        }
+    }
+
+    fn get_var(&self, ident: &str) -> Result<&GenVariable, Diagnostic> {
         match self.var_map.get(ident) {
             Some(idx) => Ok(self.stack.get(*idx).unwrap()),
             None => err!("Variable: {ident:?} doesn't exist."),
@@ -0,0 +1,285 @@
+// >>DIAGNOSTIC<< What every stage returns when something goes wrong, instead of a formatted String.
+//  - severity, a code per stage, a primary span and optional labels, notes and help.
+//  - codes: E01xx lexer, E02xx parser, E03xx semantic, E04xx codegen, E05xx link.
+//  - 'render' prints it like rustc, the offending source line with the span underlined.
+//  - 'to_json' is one line of JSON, for editors and CI.
+use crate::span::{SourceMap, Span};
+use std::fmt;
+
+// ANSI escapes, only used when rendering to a terminal.
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const GREEN: &str = "\x1b[1;32m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: u16,
+    pub message: String,
+    pub span: Option<Span>,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: u16, message: String) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code,
+            message,
+            span: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn error(code: u16, message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: u16, message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    pub fn with_span(mut self, span: Span) -> Diagnostic {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Diagnostic {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Diagnostic {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Diagnostic {
+        self.help = Some(help.into());
+        self
+    }
+
+    // error[E0200]: expected 'SemiColon', found 'CloseBrace'
+    //  --> examples/example.tc:3:1
+    //   |
+    // 3 | }
+    //   | ^
+    //   = note: ..
+    pub fn render(&self, sources: &SourceMap, color: bool) -> String {
+        let paint = |style: &str, text: &str| match color {
+            true => format!("{style}{text}{RESET}"),
+            false => text.to_string(),
+        };
+        let severity_style = match self.severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+            Severity::Note => GREEN,
+        };
+
+        let mut str = format!(
+            "{}{}\n",
+            paint(
+                severity_style,
+                &format!("{}[E{:04}]", self.severity, self.code)
+            ),
+            paint(BOLD, &format!(": {}", self.message))
+        );
+
+        // the primary span is underlined with '^', labels with '-' and their message.
+        let mut marks: Vec<(Span, char, &str, &str)> = Vec::new();
+        if let Some(span) = self.span {
+            marks.push((span, '^', "", severity_style));
+        }
+        for label in &self.labels {
+            marks.push((label.span, '-', label.message.as_str(), BLUE));
+        }
+        let gutter_width = marks
+            .iter()
+            .map(|(span, ..)| {
+                let (line, _) = sources.line_col(span.file_id, span.lo);
+                line.to_string().len()
+            })
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(gutter_width);
+
+        if let Some(span) = self.span.or(self.labels.first().map(|label| label.span)) {
+            let (line, col) = sources.line_col(span.file_id, span.lo);
+            let name = &sources.file(span.file_id).name;
+            str += &format!("{gutter}{} {name}:{line}:{col}\n", paint(BLUE, "-->"));
+        }
+        if !marks.is_empty() {
+            str += &format!("{gutter} {}\n", paint(BLUE, "|"));
+        }
+
+        marks.sort_by_key(|(span, ..)| (span.file_id, span.lo));
+        let mut last_line = None;
+        for (span, marker, message, style) in &marks {
+            let (line, col) = sources.line_col(span.file_id, span.lo);
+            let text = sources.line_text(span.file_id, line);
+            if last_line != Some((span.file_id, line)) {
+                str += &format!(
+                    "{}{text}\n",
+                    paint(BLUE, &format!("{line:>gutter_width$} | "))
+                );
+                last_line = Some((span.file_id, line));
+            }
+
+            // keep tabs so the marker lines up with the source.
+            // .. a span running onto the next lines is underlined to the end of this one.
+            let indent: String = text
+                .chars()
+                .take(col as usize - 1)
+                .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+                .collect();
+            let (hi_line, hi_col) = sources.line_col(span.file_id, span.hi);
+            let width = match hi_line == line {
+                true => hi_col.saturating_sub(col),
+                false => (text.chars().count() as u32 + 1).saturating_sub(col),
+            };
+            let underline = marker.to_string().repeat(width.max(1) as usize);
+            let mark = match message.is_empty() {
+                true => underline,
+                false => format!("{underline} {message}"),
+            };
+            str += &format!(
+                "{gutter} {}{indent}{}\n",
+                paint(BLUE, "| "),
+                paint(style, &mark)
+            );
+        }
+
+        for note in &self.notes {
+            str += &format!("{gutter} {} {note}\n", paint(BOLD, "= note:"));
+        }
+        if let Some(ref help) = self.help {
+            str += &format!("{gutter} {} {help}\n", paint(BOLD, "= help:"));
+        }
+        str
+    }
+
+    // {"file":"main.tc","byte_start":31,"byte_end":32,"line":3,"column":5,"severity":"error",
+    //  "code":"E0200","message":"..","labels":[..],"notes":[..],"help":null}
+    pub fn to_json(&self, sources: &SourceMap) -> String {
+        let file = match self.span.or(self.labels.first().map(|label| label.span)) {
+            Some(span) => json_str(&sources.file(span.file_id).name),
+            None => "null".to_string(),
+        };
+        let mut str = format!("{{\"file\":{file}");
+        str += &json_location(self.span, sources);
+        str += &format!(
+            ",\"severity\":{},\"code\":\"E{:04}\",\"message\":{}",
+            json_str(&self.severity.to_string()),
+            self.code,
+            json_str(&self.message)
+        );
+
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .map(|label| {
+                format!(
+                    "{{\"message\":{}{}}}",
+                    json_str(&label.message),
+                    json_location(Some(label.span), sources)
+                )
+            })
+            .collect();
+        let notes: Vec<String> = self.notes.iter().map(|note| json_str(note)).collect();
+        let help = match self.help {
+            Some(ref help) => json_str(help),
+            None => "null".to_string(),
+        };
+        str += &format!(
+            ",\"labels\":[{}],\"notes\":[{}],\"help\":{help}}}",
+            labels.join(","),
+            notes.join(",")
+        );
+        str
+    }
+}
+
+// The byte range, line and column fields of a span, 'null' when there isn't one.
+fn json_location(span: Option<Span>, sources: &SourceMap) -> String {
+    match span {
+        Some(span) => {
+            let (line, column) = sources.line_col(span.file_id, span.lo);
+            format!(
+                ",\"byte_start\":{},\"byte_end\":{},\"line\":{line},\"column\":{column}",
+                span.lo, span.hi
+            )
+        }
+        None => ",\"byte_start\":null,\"byte_end\":null,\"line\":null,\"column\":null".to_string(),
+    }
+}
+
+fn json_str(str: &str) -> String {
+    let mut json = String::with_capacity(str.len() + 2);
+    json.push('"');
+    for ch in str.chars() {
+        match ch {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            '\r' => json += "\\r",
+            '\t' => json += "\\t",
+            ch if (ch as u32) < 0x20 => json += &format!("\\u{:04x}", ch as u32),
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+        }
+    }
+}
+
+// Without a source map only the byte offsets are known, 'render' is the readable one.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[E{:04}]: {}", self.severity, self.code, self.message)?;
+        if let Some(span) = self.span {
+            write!(f, "\n --> {span:?}")?;
+        }
+        for label in &self.labels {
+            write!(f, "\n --> {:?}: {}", label.span, label.message)?;
+        }
+        for note in &self.notes {
+            write!(f, "\n  = note: {note}")?;
+        }
+        if let Some(ref help) = self.help {
+            write!(f, "\n  = help: {help}")?;
+        }
+        Ok(())
+    }
+}
@@ -1,24 +1,25 @@
-use crate::{debug, err};
+use crate::{debug, diagnostic::Diagnostic, err, span::Span};
 use bitflags::bitflags;
 use core::fmt;
 use std::collections::{HashMap, VecDeque};
 
 const LOG_DEBUG_INFO: bool = false;
-const MSG: &'static str = "LEX";
+const CODE: u16 = 100;
+const MSG: &str = "LEX";
+const NEGATIVE_INDENT: usize = 2; // spaces per level out from the deepest code
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TokenKind {
     // Generic Symbols
-    Comma,             // ","
-    Colon,             // ":"
-    SemiColon,         // ";"
-    OpenParen,         // "("
-    CloseParen,        // ")"
-    LineComment,       // "//"
-    OpenBrace,         // "{"
-    CloseBrace,        // "}"
-    OpenMultiComment,  // "/*"
-    CloseMultiComment, // "*/"
+    Comma,      // ","
+    Colon,      // ":"
+    SemiColon,  // ";"
+    OpenParen,  // "("
+    CloseParen, // ")"
+    OpenBrace,  // "{"
+    CloseBrace, // "}"
+    Underscore, // "_" discards a result, '_ = f();'
+    DocComment, // "///" trivia, documents the next 'fn' or 'let'
 
     // Operators
     Array,     // "[]"
@@ -36,6 +37,8 @@ pub enum TokenKind {
     Shl,       // "<<"
     Shr,       // ">>"
     Arrow,     //  "->"
+    DotDot,    // ".." range, end excluded
+    DotDotEq,  // "..=" range, end included
 
     // Combo Assign
     AddEq,    // "+="
@@ -67,16 +70,26 @@ pub enum TokenKind {
     If,
     Else,
     While,
+    For,
+    In,
     Break,
+    Continue,
     Mut,
     Fn,
     Return,
     True,
     False,
+    As,
 
     // Primitive Constructs
     Ident,
     IntLit,
+    FloatLit,
+    StrLit,
+    CharLit,
+    Label, // "'outer", the value keeps its '\''
+    Error, // input that isn't a token, already reported, the value is its text
+    Eof,   // only in lossless mode, holds the trivia after the last token
 }
 
 #[derive(Debug)]
@@ -148,6 +161,7 @@ impl TokenKind {
     // .. c++ associativity: https://en.wikipedia.org/wiki/Operators_in_C_and_C%2B%2B#Operator_precedence
     pub fn get_prec_binary(&self) -> i32 {
         match self {
+            TokenKind::As => 13, // below unary, '-x as i64' is '(-x) as i64'
             TokenKind::Mul | TokenKind::Quo | TokenKind::Mod => 12,
             TokenKind::Sub | TokenKind::Add => 11,
             TokenKind::Shl | TokenKind::Shr => 10,
@@ -159,7 +173,6 @@ impl TokenKind {
             TokenKind::CmpAnd => 3,
             TokenKind::CmpOr => 2,
             _ if self.has_flags(TokenFlags::ASSIGN) => 1,
-            TokenKind::Comma => 0,
             _ => -100,
         }
     }
@@ -176,7 +189,7 @@ impl TokenKind {
         }
     }
 
-    pub fn assign_to_arithmetic(&self) -> Result<TokenKind, String> {
+    pub fn assign_to_arithmetic(&self) -> Result<TokenKind, Diagnostic> {
         match self {
             TokenKind::AddEq => Ok(TokenKind::Add),
             TokenKind::SubEq => Ok(TokenKind::Sub),
@@ -205,39 +218,110 @@ impl TokenKind {
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum BufKind {
     Word,
-    IntLit,
     Symbol,
     Illegal,
-    NewLine,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Token {
     pub kind: TokenKind,
     pub value: Option<String>,
-    pub pos: (u32, u32),
+    pub span: Span,
+    pub trivia: Option<Box<Trivia>>, // only in lossless mode, see 'Lexer::lossless'
+}
+
+// A token's exact source text and the trivia around it, 'leading + text + trailing'.
+// .. trailing trivia runs up to and including the end of the token's line,
+// .. anything after that leads the next token.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Trivia {
+    pub leading: Vec<TriviaPiece>,
+    pub text: String,
+    pub trailing: Vec<TriviaPiece>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TriviaPiece {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TriviaKind {
+    Whitespace,
+    LineComment,
+    BlockComment,
+}
+
+// Joins a lossless token stream back into the source it was lexed from, byte for byte.
+pub fn to_source(tokens: &VecDeque<Token>) -> String {
+    let mut src = String::new();
+    for trivia in tokens.iter().filter_map(|tok| tok.trivia.as_deref()) {
+        trivia.leading.iter().for_each(|piece| src += &piece.text);
+        src += &trivia.text;
+        trivia.trailing.iter().for_each(|piece| src += &piece.text);
+    }
+    src
 }
 
 impl Token {
     pub fn as_str(&self) -> &str {
         match self.value {
             Some(ref str) => str,
-            None => panic!("expected value in: {self:#?}"),
+            None => panic!("expected value in '{:?}' token", self.kind),
         }
     }
+
+    // An 'IntLit's value and type suffix, e.g "255u8" => (255, Some("u8")).
+    pub fn int_lit(&self) -> (u64, Option<&str>) {
+        let str = self.as_str();
+        let (digits, suffix) = str.split_at(
+            str.find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(str.len()),
+        );
+        let suffix = match suffix.is_empty() {
+            true => None,
+            false => Some(suffix),
+        };
+        (digits.parse().unwrap(), suffix)
+    }
+
+    // '1.5f32' => (1.5, Some("f32")), the value never holds letters besides an exponent's 'e'.
+    pub fn float_lit(&self) -> (f64, Option<&str>) {
+        let str = self.as_str();
+        let (number, suffix) = str.split_at(
+            str.find(|ch: char| ch.is_ascii_alphabetic() && ch != 'e')
+                .unwrap_or(str.len()),
+        );
+        let suffix = match suffix.is_empty() {
+            true => None,
+            false => Some(suffix),
+        };
+        (number.parse().unwrap(), suffix)
+    }
 }
 
-pub struct Lexer {
-    idx: usize,
-    pos: (u32, u32),
-    input: Vec<u8>,
+// Lexes 'input' lazily, one token per 'next', so the source is never copied or buffered.
+pub struct Lexer<'a> {
+    idx: usize, // byte offset into 'input', always on a char boundary between tokens
+    file_id: u32,
+    input: &'a str,
     reg: HashMap<&'static str, TokenKind>,
-    is_linecomment: bool,
-    is_multicomment: bool,
+    unicode_idents: bool,
+    lossless: bool,
+    negative_whitespace: bool,
+    no_uppercase: bool,
+    depth: usize,                      // of braces, at 'idx'
+    lines: Vec<(usize, usize, usize)>, // negative whitespace, each line's start, first token && depth
+    leading: Vec<TriviaPiece>,         // lossless, trivia for the next token
+    next: Option<Token>, // lossless, lexed while finding the last token's trailing trivia
+    eof: bool,           // lossless, the 'Eof' token was given out
+    errors: Vec<Diagnostic>,
 }
 
-impl Lexer {
-    pub fn new(input: Vec<String>) -> Lexer {
+impl<'a> Lexer<'a> {
+    pub fn new(file_id: u32, input: &'a str) -> Lexer<'a> {
         let reg: HashMap<&'static str, TokenKind> = HashMap::from([
             // Generic Symbols
             (",", TokenKind::Comma),
@@ -247,9 +331,7 @@ impl Lexer {
             (")", TokenKind::CloseParen),
             ("{", TokenKind::OpenBrace),
             ("}", TokenKind::CloseBrace),
-            ("//", TokenKind::LineComment),
-            ("/*", TokenKind::OpenMultiComment),
-            ("*/", TokenKind::CloseMultiComment),
+            ("_", TokenKind::Underscore),
             // Operators
             ("!", TokenKind::CmpNot),
             ("^", TokenKind::Ptr),
@@ -266,6 +348,8 @@ impl Lexer {
             ("<<", TokenKind::Shl),
             (">>", TokenKind::Shr),
             ("->", TokenKind::Arrow),
+            ("..", TokenKind::DotDot),
+            ("..=", TokenKind::DotDotEq),
             // Combo Assign
             ("+=", TokenKind::AddEq),
             ("-=", TokenKind::SubEq),
@@ -296,187 +380,819 @@ impl Lexer {
             ("else", TokenKind::Else),
             ("mut", TokenKind::Mut),
             ("while", TokenKind::While),
+            ("for", TokenKind::For),
+            ("in", TokenKind::In),
             ("break", TokenKind::Break),
+            ("continue", TokenKind::Continue),
             ("true", TokenKind::True),
             ("false", TokenKind::False),
+            ("as", TokenKind::As),
         ]);
         Lexer {
             idx: 0,
-            pos: (0, 0),
-            input: input
-                .iter()
-                .map(|x| x.chars())
-                .flatten()
-                .map(|x| x as u8)
-                .collect(),
+            file_id,
+            input,
             reg,
-            is_linecomment: false,
-            is_multicomment: false,
+            unicode_idents: false,
+            lossless: false,
+            negative_whitespace: false,
+            no_uppercase: false,
+            depth: 0,
+            lines: Vec::new(),
+            leading: Vec::new(),
+            next: None,
+            eof: false,
+            errors: Vec::new(),
         }
     }
 
-    pub fn tokenize(&mut self) -> VecDeque<Token> {
-        let mut tokens = VecDeque::new();
+    // Allow identifiers like 'größe', any alphabetic char starts one and alphanumerics continue it.
+    // .. close to unicode's XID_Start && XID_Continue, without pulling in their tables.
+    // .. they're always lexed as one identifier, an error unless this is set.
+    pub fn unicode_idents(mut self, allow: bool) -> Lexer<'a> {
+        self.unicode_idents = allow;
+        self
+    }
+
+    // Keep whitespace and comments, every token gets its 'Trivia' and the stream ends in an 'Eof'.
+    // .. for source tools like a formatter, 'to_source' gives back the exact input.
+    // .. the parser accepts either stream.
+    pub fn lossless(mut self, keep: bool) -> Lexer<'a> {
+        self.lossless = keep;
+        self
+    }
+
+    // Joke mode, indentation is significant and negative: the deepest code has none,
+    // .. each enclosing level is indented another 'NEGATIVE_INDENT' spaces.
+    // .. checked once the whole input is lexed, the deepest level isn't known before.
+    pub fn negative_whitespace(mut self, check: bool) -> Lexer<'a> {
+        self.negative_whitespace = check;
+        self
+    }
+
+    // Lint, identifiers can't have uppercase letters.
+    pub fn no_uppercase(mut self, deny: bool) -> Lexer<'a> {
+        self.no_uppercase = deny;
+        self
+    }
+
+    // Bad tokens are reported and lexing carries on, every error is returned with the tokens.
+    // .. input that isn't a token becomes an 'Error' token, so nothing is dropped and parsing can go on.
+    // .. the whole stream at once, iterate the lexer to get a token at a time.
+    pub fn tokenize(&mut self) -> (VecDeque<Token>, Vec<Diagnostic>) {
+        let tokens = self.by_ref().collect();
+        (tokens, self.take_errors())
+    }
+
+    // Every error found so far, each also left an 'Error' token in the stream.
+    pub fn take_errors(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.errors)
+    }
+
+    // The next token, with the trivia before it in lossless mode.
+    fn lex_token(&mut self) -> Option<Token> {
         while self.idx < self.input.len() {
+            let start = self.idx;
             match self.next_token() {
-                Some(tok) => match tok.kind {
-                    TokenKind::LineComment => self.is_linecomment = true,
-                    TokenKind::OpenMultiComment => self.is_multicomment = true,
-                    TokenKind::CloseMultiComment => self.is_multicomment = false,
-                    _ if self.is_multicomment => (),
-                    _ => {
-                        tokens.push_back(tok);
-                        debug!(self, "new tok: {:?}", tokens.back().as_ref().unwrap());
-                    }
-                },
-                None => continue,
+                Some(tok) => return Some(self.finish_tok(tok, start)),
+                None if self.lossless => {
+                    let piece = self.trivia(start, self.idx);
+                    self.leading.push(piece);
+                }
+                None => (),
+            }
+        }
+
+        self.check_indents();
+        if !self.lossless || self.eof {
+            return None;
+        }
+        self.eof = true;
+        let end = self.input.len() as u32;
+        let tok = Token {
+            kind: TokenKind::Eof,
+            value: None,
+            span: Span::new(self.file_id, end, end),
+            trivia: None,
+        };
+        Some(self.finish_tok(tok, self.idx))
+    }
+
+    // Every token lexed passes through here, to attach its trivia and note its line's indentation.
+    fn finish_tok(&mut self, mut tok: Token, start: usize) -> Token {
+        if self.negative_whitespace && tok.kind != TokenKind::Eof {
+            self.track_indent(&tok);
+        }
+        if self.lossless {
+            tok.trivia = Some(Box::new(Trivia {
+                leading: std::mem::take(&mut self.leading),
+                text: self.input[start..self.idx].to_string(),
+                trailing: Vec::new(),
+            }));
+        }
+        tok
+    }
+
+    fn track_indent(&mut self, tok: &Token) {
+        if tok.kind == TokenKind::CloseBrace {
+            self.depth = self.depth.saturating_sub(1);
+        }
+        let lo = tok.span.lo as usize;
+        let line_start = self.input[..lo].rfind('\n').map_or(0, |nl| nl + 1);
+        if self.input[line_start..lo]
+            .chars()
+            .all(|ch| ch.is_whitespace())
+        {
+            self.lines.push((line_start, lo, self.depth));
+        }
+        if tok.kind == TokenKind::OpenBrace {
+            self.depth += 1;
+        }
+    }
+
+    // Each line's indentation against its brace depth, e.g three levels deep:
+    //            fn main() {
+    //          if x {
+    //        while y {
+    //      exit(1);
+    //        }
+    //          }
+    //            }
+    // .. a '}' is at the depth of the '{' it closes.
+    fn check_indents(&mut self) {
+        let lines = std::mem::take(&mut self.lines);
+        let Some(deepest) = lines.iter().map(|(_, _, depth)| *depth).max() else {
+            return;
+        };
+        for (line_start, lo, depth) in lines {
+            let indent = &self.input[line_start..lo];
+            let expected = (deepest - depth) * NEGATIVE_INDENT;
+            if indent.len() == expected && !indent.contains('\t') {
+                continue;
+            }
+
+            let span = match indent.is_empty() {
+                true => Span::new(self.file_id, lo as u32, lo as u32 + 1),
+                false => Span::new(self.file_id, line_start as u32, lo as u32),
             };
+            let msg = match indent.contains('\t') {
+                true => "Indented with a tab, negative whitespace counts spaces".to_string(),
+                false => format!("Indented by {}, expected {expected}", indent.len()),
+            };
+            let note = match deepest - depth {
+                0 => "this line is the deepest code".to_string(),
+                out => format!("this line is {out} level(s) out from the deepest code"),
+            };
+            let e = Diagnostic::error(CODE, msg)
+                .with_span(span)
+                .with_note(note)
+                .with_help(format!(
+                    "the deepest code has no indentation, each level out adds {NEGATIVE_INDENT} spaces"
+                ));
+            self.errors.push(e);
+        }
+    }
+
+    // Lexes on to the end of 'tok's line for its trailing trivia, a token found first is kept for 'next'.
+    // .. whitespace is split after its newline, the rest leads the next token.
+    fn lex_trailing(&mut self, tok: &mut Token) {
+        let trivia = tok.trivia.as_deref_mut().unwrap();
+        while self.idx < self.input.len() {
+            let start = self.idx;
+            if let Some(next) = self.next_token() {
+                self.next = Some(self.finish_tok(next, start));
+                return;
+            }
+
+            let piece = self.trivia(start, self.idx);
+            match piece.text.find('\n') {
+                Some(nl) if piece.kind == TriviaKind::Whitespace => {
+                    let split = start + nl + 1;
+                    trivia.trailing.push(self.trivia(start, split));
+                    if split < self.idx {
+                        self.leading.push(self.trivia(split, self.idx));
+                    }
+                    return;
+                }
+                _ => trivia.trailing.push(piece),
+            }
         }
-        tokens
     }
 
     fn next_token(&mut self) -> Option<Token> {
-        let mut buf = Vec::new();
+        let start = self.idx;
+        let mut buf = String::new();
         let mut buf_kind = BufKind::Illegal;
 
-        loop {
-            let next_char = match self.peek(0) {
-                Some(char) => char,
-                None => break,
-            };
-
+        while let Some(next_char) = self.peek_char() {
             // the order of these match statements matter!
             let char_type = match next_char {
-                b'\n' => BufKind::NewLine,
-                _ if self.is_linecomment || next_char.is_ascii_whitespace() => BufKind::Illegal, // collect together all the illegal stuff at once!
-                b'0'..=b'9' | b'_' if buf_kind == BufKind::Word => BufKind::Word,
-                b'_' if buf_kind == BufKind::IntLit => continue, // skip number spacing, e.g 1_000_000 => 1000000
-                b'0'..=b'9' => BufKind::IntLit,
-                b'a'..=b'z' | b'A'..=b'Z' => BufKind::Word,
-                b'!'..=b'/' | b':'..=b'@' | b'['..=b'`' | b'{'..=b'~' => BufKind::Symbol,
-                _ => {
-                    let err_msg: Result<bool, String> = err!("unknown char found {next_char}");
-                    panic!("{err_msg:?}");
+                _ if next_char.is_ascii_whitespace() => BufKind::Illegal, // collect together all the illegal stuff at once!
+                '/' if matches!(self.peek(1), Some(b'/' | b'*')) => match buf.is_empty() {
+                    true => return self.match_comment(start),
+                    false => break,
+                },
+                '*' if self.peek(1) == Some(b'/') => match buf.is_empty() {
+                    true => {
+                        self.idx += 2;
+                        let e = Diagnostic::error(CODE, "Unmatched '*/'".to_string())
+                            .with_span(self.span_from(start))
+                            .with_help("no block comment is open here, remove it");
+                        self.errors.push(e);
+                        return Some(self.error_tok(start));
+                    }
+                    false => break,
+                },
+                '"' => match buf.is_empty() {
+                    true => return Some(self.match_str(start)),
+                    false => break,
+                },
+                '\'' => match buf.is_empty() {
+                    true if self.at_label() => return Some(self.match_label(start)),
+                    true => return Some(self.match_char(start)),
+                    false => break,
+                },
+                '0'..='9' | '_' if buf_kind == BufKind::Word => BufKind::Word,
+                // '_foo' is an identifier, only a lone '_' is the discard symbol.
+                '_' if buf.is_empty()
+                    && matches!(self.input[self.idx + 1..].chars().next(),
+                        Some(ch) if ch.is_alphanumeric() || ch == '_') =>
+                {
+                    BufKind::Word
                 }
+                '0'..='9' => match buf.is_empty() {
+                    true => return Some(self.match_int(start)),
+                    false => break,
+                },
+                'a'..='z' | 'A'..='Z' => BufKind::Word,
+                '!'..='/' | ':'..='@' | '['..='`' | '{'..='~' => BufKind::Symbol,
+                _ if next_char.is_alphabetic() => BufKind::Word,
+                _ if next_char.is_alphanumeric() && buf_kind == BufKind::Word => BufKind::Word,
+                _ => match buf.is_empty() {
+                    true => return Some(self.match_unknown(start)),
+                    false => break,
+                },
             };
 
             // buf_kind not set, set it.
             if buf.is_empty() {
-                buf_kind = char_type.clone();
+                buf_kind = char_type;
             } else if char_type != buf_kind {
                 break;
             }
 
-            let ch = self.consume();
-            buf.push(ch);
+            buf.push(self.consume_char());
         }
-        self.create_tok(buf_kind, &buf)
+        self.create_tok(buf_kind, buf, start)
     }
 
     // TO FUTURE TOM: for future stuff, create a new bufkind and do stuff here.
     //  - trying to modify state in next_token causes bugs.
     //      .. because after creating a token, the next char may not be "next_char" due to a reduce
-    //      .. !! watchout for repeats, e.g on newline buf: self.pos.1 += collected_newlines
-    fn create_tok(&mut self, buf_kind: BufKind, buf: &Vec<u8>) -> Option<Token> {
-        if buf.is_empty() {
+    //  - tokens span from their first char, 'start', up to 'self.idx' after any reduce.
+    fn create_tok(&mut self, buf_kind: BufKind, buf_str: String, start: usize) -> Option<Token> {
+        if buf_str.is_empty() {
             self.idx += 1;
-            self.pos.0 += 1;
             return None;
         }
 
-        let buf_str: String = buf.into_iter().map(|x| *x as char).collect();
-        debug!(
-            self,
-            "buf: '{buf_str}', kind: {buf_kind:?} | pos: {}", self.idx
-        );
+        debug!("buf: '{buf_str}', kind: {buf_kind:?} | pos: {}", self.idx);
 
         match buf_kind {
             BufKind::Illegal => None,
-            BufKind::NewLine => {
-                self.is_linecomment = false;
-                self.pos.1 += buf.len() as u32;
-                self.pos.0 = 0;
-                None
-            }
-            BufKind::Word => self.match_word(buf_str),
-            BufKind::Symbol => self.match_symbol(buf_str),
-            BufKind::IntLit => Some(Token {
-                kind: TokenKind::IntLit,
-                value: Some(buf_str),
-                pos: (self.pos.0, self.pos.1),
-            }),
+            BufKind::Word => self.match_word(buf_str, start),
+            BufKind::Symbol => self.match_symbol(buf_str, start),
         }
     }
 
-    fn match_word(&self, buf_str: String) -> Option<Token> {
+    fn match_word(&mut self, buf_str: String, start: usize) -> Option<Token> {
+        if !self.unicode_idents && !buf_str.is_ascii() {
+            let e = Diagnostic::error(CODE, format!("Non-ascii identifier '{buf_str}'"))
+                .with_span(self.span_from(start))
+                .with_help("unicode identifiers are opt-in, enable them with '--unicode-idents'");
+            self.errors.push(e);
+        }
         match self.reg.get(buf_str.as_str()) {
             Some(kind) => Some(Token {
-                kind: kind.clone(),
+                kind: *kind,
                 value: None,
-                pos: (self.pos.0, self.pos.1),
-            }),
-            None => Some(Token {
-                kind: TokenKind::Ident,
-                value: Some(buf_str),
-                pos: (self.pos.0, self.pos.1),
+                span: self.span_from(start),
+                trivia: None,
             }),
+            None => {
+                if self.no_uppercase && buf_str.chars().any(|ch| ch.is_uppercase()) {
+                    let e = Diagnostic::error(CODE, format!("Uppercase identifier '{buf_str}'"))
+                        .with_span(self.span_from(start))
+                        .with_help(format!(
+                            "'--no-uppercase' is on, write it as '{}'",
+                            buf_str.to_lowercase()
+                        ));
+                    self.errors.push(e);
+                }
+                Some(Token {
+                    kind: TokenKind::Ident,
+                    value: Some(buf_str),
+                    span: self.span_from(start),
+                    trivia: None,
+                })
+            }
         }
     }
 
-    fn match_symbol(&mut self, mut buf_str: String) -> Option<Token> {
+    fn match_symbol(&mut self, mut buf_str: String, start: usize) -> Option<Token> {
         while !buf_str.is_empty() {
             match self.reg.get(buf_str.as_str()) {
                 Some(kind) => {
                     return Some(Token {
                         kind: *kind,
                         value: None,
-                        pos: (self.pos.0, self.pos.1),
+                        span: self.span_from(start),
+                        trivia: None,
                     });
                 }
                 None => {
                     buf_str.pop();
                     self.idx -= 1;
-                    debug!(self, "reduce {} | new pos: {}", buf_str, self.idx);
+                    debug!("reduce {} | new pos: {}", buf_str, self.idx);
                 }
             }
         }
-        self.idx += 1;
-        self.pos.0 += 1;
+        Some(self.match_unknown(start))
+    }
+
+    fn trivia(&self, lo: usize, hi: usize) -> TriviaPiece {
+        let text = &self.input[lo..hi];
+        let kind = match text {
+            _ if text.starts_with("//") => TriviaKind::LineComment,
+            _ if text.starts_with("/*") => TriviaKind::BlockComment,
+            _ => TriviaKind::Whitespace,
+        };
+        TriviaPiece {
+            kind,
+            text: text.to_string(),
+            span: Span::new(self.file_id, lo as u32, hi as u32),
+        }
+    }
+
+    // '//' runs to the end of the line, '///' is a doc comment whose value is the text after it.
+    // .. '/*' .. '*/' nests, '/* /* */ */' is one comment, an unclosed one runs to the end of the file.
+    fn match_comment(&mut self, start: usize) -> Option<Token> {
+        if self.peek(1) == Some(b'/') {
+            let line = self.input[start..].lines().next().unwrap_or("");
+            self.idx += line.len();
+            return match line.strip_prefix("///") {
+                Some(doc) if !doc.starts_with('/') => Some(Token {
+                    kind: TokenKind::DocComment,
+                    value: Some(doc.strip_prefix(' ').unwrap_or(doc).to_string()),
+                    span: self.span_from(start),
+                    trivia: None,
+                }),
+                _ => None,
+            };
+        }
+
+        let mut opened = Vec::new(); // start of each '/*' not yet closed
+        while self.idx < self.input.len() {
+            match (self.peek(0), self.peek(1)) {
+                (Some(b'/'), Some(b'*')) => {
+                    opened.push(self.idx);
+                    self.idx += 2;
+                }
+                (Some(b'*'), Some(b'/')) => {
+                    opened.pop();
+                    self.idx += 2;
+                    if opened.is_empty() {
+                        return None;
+                    }
+                }
+                _ => _ = self.consume_char(),
+            }
+        }
+
+        let open_span = |lo: usize| Span::new(self.file_id, lo as u32, lo as u32 + 2);
+        let mut e = Diagnostic::error(CODE, "Unterminated block comment".to_string())
+            .with_span(open_span(opened[0]));
+        for nested in &opened[1..] {
+            e = e.with_label(open_span(*nested), "nested comment, also needs a '*/'");
+        }
+        self.errors
+            .push(e.with_help("close it with '*/', block comments nest"));
         None
     }
 
+    // A char that can't start any token, e.g '$', a lone '[', a control char or a non-ascii '☃'.
+    fn match_unknown(&mut self, start: usize) -> Token {
+        let ch = self.consume_char();
+        let mut e = Diagnostic::error(
+            CODE,
+            format!(
+                "Unexpected character '{}' (U+{:04X})",
+                ch.escape_debug(),
+                ch as u32
+            ),
+        )
+        .with_span(self.span_from(start));
+        e = match ch {
+            '[' | ']' => e.with_help("brackets only appear together, in an array type: '[]'"),
+            _ if !ch.is_ascii() => e.with_note("unicode is allowed in strings and comments"),
+            _ => e,
+        };
+        self.errors.push(e);
+        self.error_tok(start)
+    }
+
+    fn error_tok(&self, start: usize) -> Token {
+        Token {
+            kind: TokenKind::Error,
+            value: Some(self.input[start..self.idx].to_string()),
+            span: self.span_from(start),
+            trivia: None,
+        }
+    }
+
+    // '255', '0xFF', '0b1010' or '0o17', with '_' separators and an optional type suffix: '255u8'.
+    // .. the token's value is the number in decimal followed by its suffix, checked by semantic.
+    // .. a fraction, an exponent or a float suffix makes it a float instead, see 'match_float'.
+    fn match_int(&mut self, start: usize) -> Token {
+        let (radix, base) = match (self.peek(0), self.peek(1)) {
+            (Some(b'0'), Some(b'x')) => (16, "hexadecimal"),
+            (Some(b'0'), Some(b'o')) => (8, "octal"),
+            (Some(b'0'), Some(b'b')) => (2, "binary"),
+            _ => (10, "decimal"),
+        };
+        if radix != 10 {
+            self.consume();
+            self.consume();
+        }
+
+        // skip number spacing, e.g 1_000_000 => 1000000
+        let mut digits = String::new();
+        while let Some(ch) = self.peek(0) {
+            match ch {
+                b'_' => _ = self.consume(),
+                _ if (ch as char).is_digit(radix) => digits.push(self.consume() as char),
+                _ => break,
+            }
+        }
+        let digits_end = self.idx;
+        let is_float = match (self.peek(0), self.peek(1), self.peek(2)) {
+            (Some(b'.'), Some(next), _) => next.is_ascii_digit(),
+            (Some(b'e' | b'E'), Some(b'+' | b'-'), Some(next)) => next.is_ascii_digit(),
+            (Some(b'e' | b'E'), Some(next), _) => next.is_ascii_digit(),
+            _ => false,
+        };
+        if radix == 10 && is_float {
+            return self.match_float(start, digits);
+        }
+        let mut suffix = String::new();
+        while let Some(ch) = self
+            .peek(0)
+            .filter(|ch| ch.is_ascii_alphanumeric() || *ch == b'_')
+        {
+            suffix.push(self.consume() as char);
+        }
+
+        if radix == 10 && matches!(suffix.as_str(), "f32" | "f64") {
+            return self.float_tok(start, &digits, suffix);
+        }
+
+        let value = match u64::from_str_radix(&digits, radix) {
+            _ if suffix.starts_with(|ch: char| ch.is_ascii_digit()) => {
+                let digit = suffix.chars().next().unwrap();
+                let span = Span::new(self.file_id, digits_end as u32, digits_end as u32 + 1);
+                err!(span, "Invalid digit '{digit}' in {base} literal")
+            }
+            _ if digits.is_empty() => err!(self.span_from(start), "No digits in {base} literal"),
+            Ok(value) => Ok(value),
+            Err(_) => err!(
+                self.span_from(start),
+                "Integer literal is too large, the largest is {}",
+                u64::MAX
+            ),
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                self.errors.push(e);
+                suffix.clear();
+                0
+            }
+        };
+
+        Token {
+            kind: TokenKind::IntLit,
+            value: Some(format!("{value}{suffix}")),
+            span: self.span_from(start),
+            trivia: None,
+        }
+    }
+
+    // '1.5', '2e10' or '3.0f32', picking up after 'match_int' has read the whole number part.
+    // .. a '.' must be followed by a digit, so '0..5' stays an int, two dots and an int.
+    fn match_float(&mut self, start: usize, mut number: String) -> Token {
+        if self.peek(0) == Some(b'.') {
+            number.push(self.consume() as char);
+            self.consume_digits(&mut number);
+        }
+        if let Some(b'e' | b'E') = self.peek(0) {
+            self.consume();
+            number.push('e');
+            if let Some(b'+' | b'-') = self.peek(0) {
+                number.push(self.consume() as char);
+            }
+            self.consume_digits(&mut number);
+        }
+        let mut suffix = String::new();
+        while let Some(ch) = self
+            .peek(0)
+            .filter(|ch| ch.is_ascii_alphanumeric() || *ch == b'_')
+        {
+            suffix.push(self.consume() as char);
+        }
+        self.float_tok(start, &number, suffix)
+    }
+
+    // the token's value is the number, as rust would print it, followed by its suffix.
+    fn float_tok(&mut self, start: usize, number: &str, mut suffix: String) -> Token {
+        let value = match number.parse::<f64>() {
+            _ if suffix.starts_with(['e', 'E']) => {
+                err!(self.span_from(start), "Expected digits after the exponent")
+            }
+            Ok(value) if value.is_infinite() => {
+                err!(
+                    self.span_from(start),
+                    "Float literal is too large for 'f64'"
+                )
+            }
+            Ok(value) => Ok(value),
+            Err(_) => err!(self.span_from(start), "Invalid float literal"),
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                self.errors.push(e);
+                suffix.clear();
+                0.0
+            }
+        };
+
+        Token {
+            kind: TokenKind::FloatLit,
+            value: Some(format!("{value:?}{suffix}")),
+            span: self.span_from(start),
+            trivia: None,
+        }
+    }
+
+    // digits with '_' separators, e.g 1_000_000 => 1000000
+    fn consume_digits(&mut self, buf: &mut String) {
+        while let Some(ch) = self.peek(0) {
+            match ch {
+                b'_' => _ = self.consume(),
+                b'0'..=b'9' => buf.push(self.consume() as char),
+                _ => break,
+            }
+        }
+    }
+
+    // '"' .. '"', the token's value is the string with its escapes replaced.
+    // .. strings can't span lines, an unterminated one is reported and ends with its line.
+    fn match_str(&mut self, start: usize) -> Token {
+        self.consume(); // opening '"'
+        let mut bytes = Vec::new();
+        loop {
+            match self.peek(0) {
+                Some(b'"') => {
+                    self.consume();
+                    break;
+                }
+                Some(b'\\') if !matches!(self.peek(1), None | Some(b'\n')) => {
+                    let escape_start = self.idx;
+                    self.consume();
+                    match self.match_escape(escape_start) {
+                        Ok(byte) => bytes.push(byte),
+                        Err(e) => self.errors.push(e),
+                    }
+                }
+                Some(b'\n') | None => {
+                    let e = Diagnostic::error(CODE, "Unterminated string literal".to_string())
+                        .with_span(self.span_from(start))
+                        .with_help("add a closing '\"', use '\\n' for a new line in a string");
+                    self.errors.push(e);
+                    break;
+                }
+                Some(_) => bytes.push(self.consume()),
+            }
+        }
+
+        Token {
+            kind: TokenKind::StrLit,
+            value: Some(String::from_utf8_lossy(&bytes).into_owned()),
+            span: self.span_from(start),
+            trivia: None,
+        }
+    }
+
+    // "'outer" is a label, "'o'" a character, a label's name is never closed by a '\''.
+    fn at_label(&self) -> bool {
+        if !matches!(self.peek(1), Some(b'a'..=b'z' | b'A'..=b'Z' | b'_')) {
+            return false;
+        }
+        let mut offset = 2;
+        while matches!(self.peek(offset), Some(ch) if ch.is_ascii_alphanumeric() || ch == b'_') {
+            offset += 1;
+        }
+        self.peek(offset) != Some(b'\'')
+    }
+
+    fn match_label(&mut self, start: usize) -> Token {
+        let mut value = String::from(self.consume() as char);
+        while matches!(self.peek(0), Some(ch) if ch.is_ascii_alphanumeric() || ch == b'_') {
+            value.push(self.consume() as char);
+        }
+        Token {
+            kind: TokenKind::Label,
+            value: Some(value),
+            span: self.span_from(start),
+            trivia: None,
+        }
+    }
+
+    // '\'a\'', the token's value is the one character, with its escape replaced.
+    fn match_char(&mut self, start: usize) -> Token {
+        self.consume(); // opening '\''
+        let value = match self.peek(0) {
+            Some(b'\\') if !matches!(self.peek(1), None | Some(b'\n')) => {
+                let escape_start = self.idx;
+                self.consume();
+                self.match_escape(escape_start)
+            }
+            Some(b'\'') => err!(
+                Span::new(self.file_id, start as u32, self.idx as u32 + 1),
+                "Empty character literal"
+            ),
+            Some(b'\n') | None => err!(self.span_from(start), "Unterminated character literal"),
+            Some(_) => {
+                let ch_start = self.idx;
+                match self.consume_char() {
+                    ch if ch.is_ascii() => Ok(ch as u8),
+                    ch => Err(Diagnostic::error(
+                        CODE,
+                        format!("Non-ascii character '{ch}' in a character literal"),
+                    )
+                    .with_span(self.span_from(ch_start))
+                    .with_help("a 'char' is one byte, use a string literal for unicode text")),
+                }
+            }
+        };
+
+        // the closing '\'', anything before it on the line is a character too many.
+        let mut extra = false;
+        let closed = loop {
+            match self.peek(0) {
+                Some(b'\'') => {
+                    self.consume();
+                    break true;
+                }
+                Some(b'\n') | None => break false,
+                Some(_) => {
+                    self.consume();
+                    extra = true;
+                }
+            }
+        };
+
+        let e = match value {
+            Ok(_) if !closed => {
+                Diagnostic::error(CODE, "Unterminated character literal".to_string())
+                    .with_span(self.span_from(start))
+                    .with_help("add a closing '\\''")
+            }
+            Ok(_) if extra => Diagnostic::error(
+                CODE,
+                "Character literal may only contain one character".to_string(),
+            )
+            .with_span(self.span_from(start))
+            .with_help("use a string literal for text: \"..\""),
+            Ok(byte) => {
+                return Token {
+                    kind: TokenKind::CharLit,
+                    value: Some((byte as char).to_string()),
+                    span: self.span_from(start),
+                    trivia: None,
+                }
+            }
+            Err(e) => e,
+        };
+        self.errors.push(e);
+        Token {
+            kind: TokenKind::CharLit,
+            value: Some("\0".to_string()),
+            span: self.span_from(start),
+            trivia: None,
+        }
+    }
+
+    // The byte an escape sequence stands for, the '\' is already consumed.
+    fn match_escape(&mut self, escape_start: usize) -> Result<u8, Diagnostic> {
+        match self.consume_char() {
+            'n' => Ok(b'\n'),
+            't' => Ok(b'\t'),
+            '\\' => Ok(b'\\'),
+            '"' => Ok(b'"'),
+            '\'' => Ok(b'\''),
+            '0' => Ok(0),
+            'x' => {
+                let mut digits = String::new();
+                while digits.len() < 2 && self.peek(0).is_some_and(|ch| ch.is_ascii_hexdigit()) {
+                    digits.push(self.consume() as char);
+                }
+                let span = self.span_from(escape_start);
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(byte) if digits.len() == 2 && byte <= 0x7F => Ok(byte),
+                    Ok(_) if digits.len() == 2 => err!(
+                        span,
+                        "Out of range hex escape '\\x{digits}', must be at most '\\x7F'"
+                    ),
+                    _ => Err(Diagnostic::error(
+                        CODE,
+                        "Invalid hex escape, expected two hex digits".to_string(),
+                    )
+                    .with_span(span)
+                    .with_help("e.g. '\\x41' is 'A'")),
+                }
+            }
+            ch => Err(
+                Diagnostic::error(CODE, format!("Unknown escape sequence '\\{ch}'"))
+                    .with_span(self.span_from(escape_start))
+                    .with_help("valid escapes: '\\n' '\\t' '\\\\' '\\\"' '\\\'' '\\0' '\\xNN'"),
+            ),
+        }
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(self.file_id, start as u32, self.idx as u32)
+    }
+
     fn peek(&self, offset: usize) -> Option<u8> {
-        self.input.get(self.idx + offset).copied()
+        self.input.as_bytes().get(self.idx + offset).copied()
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.input[self.idx..].chars().next()
+    }
+
+    // the whole utf-8 sequence of the next char.
+    fn consume_char(&mut self) -> char {
+        let ch = self.peek_char().unwrap();
+        self.idx += ch.len_utf8();
+        ch
     }
 
     fn consume(&mut self) -> u8 {
         let i = self.idx;
         self.idx += 1;
-        self.pos.0 += 1;
 
-        let char = self.input.get(i).copied().unwrap();
+        let char = self.input.as_bytes().get(i).copied().unwrap();
         if char == b'\n' {
-            debug!(self, "consuming '{}'", r"\n");
+            debug!("consuming '{}'", r"\n");
         } else {
-            debug!(self, "consuming '{}'", char as char);
+            debug!("consuming '{}'", char as char);
         }
         char
     }
 }
 
+// Tokens are lexed as they're asked for, lossless mode reads ahead to the end of a token's line.
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let mut tok = match self.next.take() {
+            Some(tok) => tok,
+            None => self.lex_token()?,
+        };
+        if self.lossless && tok.kind != TokenKind::Eof {
+            self.lex_trailing(&mut tok);
+        }
+        debug!("new tok: {:?}", tok);
+        Some(tok)
+    }
+}
+
 impl fmt::Debug for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if f.alternate() {
             writeln!(f, "Token {{")?;
             writeln!(f, "    kind: {:?}", self.kind)?;
             writeln!(f, "    value: {:?}", self.value)?;
-            writeln!(f, "    pos: ({}, {})", self.pos.1 + 1, self.pos.0 + 1)?;
+            writeln!(f, "    span: {:?}", self.span)?;
             write!(f, "}}")
         } else {
             f.debug_struct("Token")
                 .field("kind", &self.kind)
                 .field("value", &self.value)
-                .field("pos", &self.pos)
+                .field("span", &self.span)
                 .finish()
         }
     }
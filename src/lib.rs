@@ -0,0 +1,98 @@
+#![allow(unused)]
+// >>LIB<< The compiler as a library, the 'rust-compiler' binary is a thin wrapper over it.
+//  - 'compile' runs source text through every stage, stopping after 'Options.emit'.
+//  - 'compile_with_warnings' does the same, also handing back the warnings of a successful compile.
+//  - each stage is usable on its own:
+//      Lexer::new(file_id, src)             -> an iterator of tokens, or '.tokenize()' them all
+//      Parser::new(tokens).parse_ast()      -> AST, 'tokens' can be the lexer itself
+//      Checker::check_ast(ast)              -> checked AST && function bodies
+//      Generator::new(checker).gen_asm()    -> NASM x86-64 asm
+//  - nothing here spawns a process, 'link' wraps nasm && ld for turning asm into an executable.
+mod macros;
+
+pub mod code_gen;
+pub mod diagnostic;
+pub mod lex;
+pub mod link;
+pub mod parse;
+pub mod semantic;
+pub mod span;
+
+pub use code_gen::Generator;
+pub use diagnostic::{Diagnostic, Severity};
+pub use lex::{Lexer, Token, TokenKind, Trivia, TriviaKind, TriviaPiece};
+pub use parse::{Parser, AST};
+pub use semantic::Checker;
+pub use span::{SourceMap, Span};
+
+use std::collections::VecDeque;
+
+// The last stage 'compile' runs, its result is the 'Output'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Emit {
+    Tokens,
+    Ast,
+    CheckedAst,
+    #[default]
+    Asm,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub emit: Emit,
+    pub unicode_idents: bool,      // see 'Lexer::unicode_idents'
+    pub negative_whitespace: bool, // see 'Lexer::negative_whitespace'
+    pub no_uppercase: bool,        // see 'Lexer::no_uppercase'
+}
+
+pub enum Output {
+    Tokens(VecDeque<Token>),
+    Ast(AST),
+    CheckedAst(Box<Checker>),
+    Asm(String),
+}
+
+// Spans in the diagnostics are for file 0, add 'source' to a new 'SourceMap' to render them.
+pub fn compile(source: &str, options: &Options) -> Result<Output, Vec<Diagnostic>> {
+    compile_with_warnings(source, options).map(|(output, _)| output)
+}
+
+// On failure the warnings follow the errors, in the same list.
+pub fn compile_with_warnings(
+    source: &str,
+    options: &Options,
+) -> Result<(Output, Vec<Diagnostic>), Vec<Diagnostic>> {
+    let mut lexer = Lexer::new(0, source)
+        .unicode_idents(options.unicode_idents)
+        .negative_whitespace(options.negative_whitespace)
+        .no_uppercase(options.no_uppercase);
+    if options.emit == Emit::Tokens {
+        let (tokens, errors) = lexer.tokenize();
+        return match errors.is_empty() {
+            true => Ok((Output::Tokens(tokens), Vec::new())),
+            false => Err(errors),
+        };
+    }
+    // tokens are lexed as the parser asks for them, never all held at once.
+    // .. bad input is an 'Error' token, parse anyway so the parser's errors are reported too.
+    let parsed = Parser::new(&mut lexer).parse_ast();
+    let mut errors = lexer.take_errors();
+    let ast = match (parsed, errors.is_empty()) {
+        (Ok(ast), true) => ast,
+        (Ok(_), false) => return Err(errors),
+        (Err(parse_errors), _) => {
+            errors.extend(parse_errors);
+            return Err(errors);
+        }
+    };
+    if options.emit == Emit::Ast {
+        return Ok((Output::Ast(ast), Vec::new()));
+    }
+    let mut checker = Checker::check_ast(ast)?;
+    let warnings = std::mem::take(&mut checker.warnings);
+    if options.emit == Emit::CheckedAst {
+        return Ok((Output::CheckedAst(Box::new(checker)), warnings));
+    }
+    let asm = Generator::new(checker).gen_asm().map_err(|e| vec![e])?;
+    Ok((Output::Asm(asm), warnings))
+}
@@ -0,0 +1,52 @@
+// >>LINK<< Turns the generated asm into an executable, using the system's 'nasm' && 'ld'.
+//  - nasm -felf64 $file.asm -o $file.o
+//  - ld $file.o -o $file
+use crate::{diagnostic::Diagnostic, err};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const CODE: u16 = 500;
+
+pub fn assemble(asm_path: &Path, obj_path: &Path) -> Result<(), Diagnostic> {
+    let mut nasm = Command::new("nasm");
+    nasm.arg("-felf64").arg(asm_path).arg("-o").arg(obj_path);
+    run_tool(&mut nasm)
+}
+
+pub fn link(obj_path: &Path, exe_path: &Path) -> Result<(), Diagnostic> {
+    let mut ld = Command::new("ld");
+    ld.arg(obj_path).arg("-o").arg(exe_path);
+    run_tool(&mut ld)
+}
+
+// Runs the executable with the compiler's stdio, 'None' if it was killed by a signal.
+pub fn run(exe_path: &Path) -> Result<Option<i32>, Diagnostic> {
+    // a bare file name would be searched for in $PATH.
+    let exe_path = match exe_path.is_relative() {
+        true => PathBuf::from(".").join(exe_path),
+        false => exe_path.to_path_buf(),
+    };
+    match Command::new(&exe_path).status() {
+        Ok(status) => Ok(status.code()),
+        Err(e) => {
+            let path = exe_path.display();
+            err!("Unable to run '{path}': {e}")
+        }
+    }
+}
+
+fn run_tool(cmd: &mut Command) -> Result<(), Diagnostic> {
+    let tool = cmd.get_program().to_string_lossy().to_string();
+    match cmd.output() {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => {
+            let status = output.status;
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = stderr.trim_end();
+            err!("'{tool}' failed ({status}):\n{stderr}")
+        }
+        Err(e) => err!("Unable to run '{tool}', is it installed? {e}"),
+    }
+}
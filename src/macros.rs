@@ -5,18 +5,9 @@ macro_rules! debug {
             println!("[DEBUG_{MSG}] {}", format!($msg))
         }
     };
-    ($self:expr, $msg:expr) => {
+    ($fmt:expr, $($arg:tt)+) => {
         if LOG_DEBUG_INFO {
-            println!(
-                "[DEBUG_{MSG};(col:{}, row:{})] {}", $self.pos.1 + 1, $self.pos.0 + 1, format!($msg)
-            )
-        }
-    };
-    ($self:expr, $fmt:expr, $($arg:tt)+) => {
-        if LOG_DEBUG_INFO {
-            println!(
-                "[DEBUG_{MSG};(col:{}, row:{})] {}", $self.pos.1 + 1, $self.pos.0 + 1, format!($fmt, $($arg)+)
-            )
+            println!("[DEBUG_{MSG}] {}", format!($fmt, $($arg)+))
         }
     };
 }
@@ -28,31 +19,24 @@ macro_rules! debugln {
             println!("\n[DEBUG_{MSG}] {}", format!($msg))
         }
     };
-    ($self:expr, $msg:expr) => {
-        if LOG_DEBUG_INFO {
-            println!(
-                "\n[DEBUG_{MSG};(col:{}, row:{})] {}", $self.pos.1 + 1, $self.pos.0 + 1, format!($msg)
-            )
-        }
-    };
-    ($self:expr, $fmt:expr, $($arg:tt)+) => {
+    ($fmt:expr, $($arg:tt)+) => {
         if LOG_DEBUG_INFO {
-            println!(
-                "\n[DEBUG_{MSG};(col:{}, row:{})] {}", $self.pos.1 + 1, $self.pos.0 + 1, format!($fmt, $($arg)+)
-            )
+            println!("\n[DEBUG_{MSG}] {}", format!($fmt, $($arg)+))
         }
     };
 }
 
+// Builds an 'Err(Diagnostic)', using the calling module's error 'CODE'.
+// .. passing a 'Span' first points the diagnostic at it.
 #[macro_export]
 macro_rules! err {
     ($msg:expr) => {
-        Err(format!("[ERROR_{MSG}] {}", format!($msg)))
+        Err($crate::diagnostic::Diagnostic::error(CODE, format!($msg)))
     };
-    ($self:expr, $msg:expr) => {
-        Err(format!("[ERROR_{MSG};(col:{}, row:{})] {}", $self.pos.1 + 1, $self.pos.0 + 1, format!($msg)))
+    ($span:expr, $msg:expr) => {
+        Err($crate::diagnostic::Diagnostic::error(CODE, format!($msg)).with_span($span))
     };
-    ($self:expr, $fmt:expr, $($arg:tt)+) => {
-        Err(format!("[ERROR_{MSG};(col:{}, row:{})] {}",  $self.pos.1 + 1, $self.pos.0 + 1, format!($fmt, $($arg)+)))
+    ($span:expr, $fmt:expr, $($arg:tt)+) => {
+        Err($crate::diagnostic::Diagnostic::error(CODE, format!($fmt, $($arg)+)).with_span($span))
     };
 }
@@ -1,158 +1,496 @@
 #![allow(unused)]
+use rust_compiler::{
+    self as compiler, link, Checker, Diagnostic, Output, Severity, SourceMap, Token, TokenKind,
+};
 use std::{
     cmp::max,
     collections::VecDeque,
     env, fs,
-    io::{BufRead, BufReader, Write},
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
 };
-mod macros;
-
-mod lex;
-use lex::*;
 
-mod parse;
-use parse::*;
+const SOURCE_EXT: &str = "tc";
+const DEFAULT_ERROR_LIMIT: usize = 20;
+const USAGE: &str = "\
+Usage: rust-compiler [OPTIONS] <INPUT>
 
-mod semantic;
-use semantic::*;
+Arguments:
+  <INPUT>        source file ('.tc'), or '-' to read from stdin
 
-mod code_gen;
-use code_gen::Generator;
+Options:
+  -o <PATH>          write the output to <PATH>, '-' for stdout
+  --emit=<STAGE>     stop after STAGE and output its result [default: exe]
+                     STAGE: tokens | ast | checked-ast | asm | obj | exe
+  --run              run the executable and report its exit status
+  --unicode-idents   allow non-ascii letters in identifiers, e.g 'größe'
+  --negative-whitespace
+                     joke mode, the deepest code has no indentation and each
+                     enclosing level is indented 2 more spaces
+  --no-uppercase     reject identifiers with uppercase letters
+  --error-limit=<N>  stop reporting errors after N of them, 0 for no limit [default: 20]
+  --message-format=<FORMAT>
+                     how errors are printed [default: human]
+                     FORMAT: human | json (one object per line)
+  -h, --help         print this message";
 
-fn main() {
+fn main() -> ExitCode {
     env::set_var("RUST_BACKTRACE", "1");
-    let file_name = get_file_name();
-    let contents = get_file_contents(&file_name);
-    // println!("\n\n{:#?}\n\n", contents);
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<ExitCode, String> {
+    let opts = match parse_args(env::args().skip(1))? {
+        Some(opts) => opts,
+        None => {
+            println!("{USAGE}");
+            return Ok(ExitCode::SUCCESS);
+        }
+    };
+    let contents = get_file_contents(&opts.input)?;
+
+    // diagnostics quote the source, coloured only when printing to a terminal.
+    let file_name = match opts.input {
+        Input::File(ref path) => path.display().to_string(),
+        Input::Stdin => "<stdin>".to_string(),
+    };
+    // 'compile' gives spans in file 0, the only file here.
+    let mut sources = SourceMap::new();
+    sources.add_file(file_name, contents);
+    let color = io::stdout().is_terminal() && io::stderr().is_terminal();
+    let error_limit = opts.error_limit;
+    let message_format = opts.message_format;
+    let report = |errors: Vec<Diagnostic>| match message_format {
+        MessageFormat::Human => fmt_diagnostics(&errors, &sources, color, error_limit),
+        MessageFormat::Json => fmt_diagnostics_json(&errors, &sources, error_limit),
+    };
+    // warnings of a successful compile, nothing was aborted so there's no summary.
+    let report_warnings = |warnings: &[Diagnostic]| match message_format {
+        MessageFormat::Human => {
+            let rendered: Vec<String> =
+                warnings.iter().map(|w| w.render(&sources, color)).collect();
+            rendered.join("\n")
+        }
+        MessageFormat::Json => fmt_diagnostics_json(warnings, &sources, 0),
+    };
+
+    let options = compiler::Options {
+        emit: opts.emit.stage(),
+        unicode_idents: opts.unicode_idents,
+        negative_whitespace: opts.negative_whitespace,
+        no_uppercase: opts.no_uppercase,
+    };
+    let (output, warnings) =
+        compiler::compile_with_warnings(&sources.file(0).src, &options).map_err(report)?;
+    if !warnings.is_empty() {
+        eprintln!("{}", report_warnings(&warnings));
+    }
+    let asm = match output {
+        Output::Asm(asm) => asm,
+        output => {
+            let str = match output {
+                Output::Tokens(tokens) => fmt_tokens(&tokens, &sources),
+                Output::Ast(ast) => format!("{ast:#?}\n"),
+                Output::CheckedAst(checker) => fmt_checked_ast(&checker),
+                Output::Asm(_) => unreachable!(),
+            };
+            write_output(opts.output.as_deref(), &str)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+    };
+
+    // '-o' names the emitted file, the other stages' files are placed next to it.
+    // .. by default next to the input, stdin has no location so use the working directory.
+    let out_path = match (opts.output, &opts.input) {
+        (Some(path), _) => path,
+        (None, Input::File(path)) => match opts.emit {
+            Emit::Asm => path.with_extension("asm"),
+            Emit::Obj => path.with_extension("o"),
+            _ => path.with_extension(""),
+        },
+        (None, Input::Stdin) => match opts.emit {
+            Emit::Asm => PathBuf::from("out.asm"),
+            Emit::Obj => PathBuf::from("out.o"),
+            _ => PathBuf::from("out"),
+        },
+    };
+    let asm_path = match opts.emit {
+        Emit::Asm => out_path.clone(),
+        _ => out_path.with_extension("asm"),
+    };
+    write_output(Some(&asm_path), &asm)?;
+    if opts.emit == Emit::Asm {
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    let tokens = Lexer::new(contents).tokenize();
-    // print_tokens(&tokens);
-    let ast = parse(tokens);
-    // println!("\n\n{ast:#?}\n\n");
-    let gen_data = semantic_check(ast);
-    println!("\n\n{:#?}\n\n", gen_data.ast);
-    code_gen(gen_data, file_name);
+    let obj_path = match opts.emit {
+        Emit::Obj => out_path.clone(),
+        _ => out_path.with_extension("o"),
+    };
+    link::assemble(&asm_path, &obj_path).map_err(|e| report(vec![e]))?;
+    if opts.emit == Emit::Obj {
+        return Ok(ExitCode::SUCCESS);
+    }
+    link::link(&obj_path, &out_path).map_err(|e| report(vec![e]))?;
+
+    if !opts.run {
+        return Ok(ExitCode::SUCCESS);
+    }
+    match link::run(&out_path).map_err(|e| report(vec![e]))? {
+        Some(code) => {
+            eprintln!(
+                "[COMPILER] '{}' exited with status {code}",
+                out_path.display()
+            );
+            Ok(ExitCode::from(code as u8))
+        }
+        None => {
+            eprintln!(
+                "[COMPILER] '{}' was terminated by a signal",
+                out_path.display()
+            );
+            Ok(ExitCode::FAILURE)
+        }
+    }
 }
 
 /*----------------------------------------------------------------------------------------
----- Stuff -------------------------------------------------------------------------------
+---- Options -----------------------------------------------------------------------------
 ----------------------------------------------------------------------------------------*/
 
-fn parse(tokens: VecDeque<Token>) -> AST {
-    // TODO(TOM): REMOVE CLONE AFTER DEBUG
-    let mut parser = Parser::new(tokens.clone());
-    match parser.parse_ast() {
-        Ok(ast) => ast,
-        Err(e) => panic!("\n{e}\n"),
-        // Err(e) => panic!("{tokens:#?}\n\n{e}\n"),
-    }
-}
-
-fn semantic_check(ast: AST) -> Checker {
-    // TODO(TOM): REMOVE CLONE AFTER DEBUG
-    match semantic::Checker::check_ast(ast.clone()) {
-        Ok(data) => data,
-        Err(e) => panic!("\n{e}\n"),
-        // Err(e) => panic!("{ast:#?}\n\n{e}\n"),
-    }
-}
-
-fn code_gen(data: Checker, file_name: String) {
-    let file_path = format!("./output/{}.asm", file_name);
-    let mut generator = Generator::new(data);
-    match generator.gen_asm() {
-        Ok(asm) => {
-            println!("[COMPILER] output placed in '{file_path}'");
-            let mut file = fs::File::create(file_path).expect("Invalid filepath given.");
-            // TODO(TOM): remove for functions impl
-            // file.write_all(
-            //     b"global _start\n\
-            //           _start:\n\
-            //          ; setup stack frame\n    \
-            //          push rbp\n    \
-            //          mov rbp, rsp\n    \
-            //          ; Program Start\n",
-            // )
-            // .unwrap();
-            file.write_all(asm.as_bytes()).unwrap();
+#[derive(Debug, Clone, PartialEq)]
+enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+// Pipeline stages, in order. Compilation stops after the emitted stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Emit {
+    Tokens,
+    Ast,
+    CheckedAst,
+    Asm,
+    Obj,
+    Exe,
+}
+
+impl Emit {
+    // Linking is done here, the library stops at asm.
+    fn stage(self) -> compiler::Emit {
+        match self {
+            Emit::Tokens => compiler::Emit::Tokens,
+            Emit::Ast => compiler::Emit::Ast,
+            Emit::CheckedAst => compiler::Emit::CheckedAst,
+            Emit::Asm | Emit::Obj | Emit::Exe => compiler::Emit::Asm,
+        }
+    }
+
+    fn from_str(stage: &str) -> Result<Emit, String> {
+        match stage {
+            "tokens" => Ok(Emit::Tokens),
+            "ast" => Ok(Emit::Ast),
+            "checked-ast" => Ok(Emit::CheckedAst),
+            "asm" => Ok(Emit::Asm),
+            "obj" => Ok(Emit::Obj),
+            "exe" => Ok(Emit::Exe),
+            _ => Err(format!(
+                "[COMPILER] Unknown emit stage '{stage}'\n\n{USAGE}"
+            )),
         }
-        Err(e) => panic!("\n{e}\n"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MessageFormat {
+    Human,
+    Json,
+}
+
+impl MessageFormat {
+    fn from_str(format: &str) -> Result<MessageFormat, String> {
+        match format {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!(
+                "[COMPILER] Unknown message format '{format}'\n\n{USAGE}"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Options {
+    input: Input,
+    output: Option<PathBuf>,
+    emit: Emit,
+    run: bool,
+    unicode_idents: bool,
+    negative_whitespace: bool,
+    no_uppercase: bool,
+    error_limit: usize,
+    message_format: MessageFormat,
+}
+
+// Returns 'None' when help was asked for, nothing to compile.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut input = None;
+    let mut output = None;
+    let mut emit = Emit::Exe;
+    let mut run = false;
+    let mut unicode_idents = false;
+    let mut negative_whitespace = false;
+    let mut no_uppercase = false;
+    let mut error_limit = DEFAULT_ERROR_LIMIT;
+    let mut message_format = MessageFormat::Human;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--run" => run = true,
+            "--unicode-idents" => unicode_idents = true,
+            "--negative-whitespace" => negative_whitespace = true,
+            "--no-uppercase" => no_uppercase = true,
+            "--emit" => match args.next() {
+                Some(stage) => emit = Emit::from_str(&stage)?,
+                None => return Err(format!("[COMPILER] '--emit' expects a stage\n\n{USAGE}")),
+            },
+            flag if flag.starts_with("--emit=") => emit = Emit::from_str(&flag["--emit=".len()..])?,
+            "--error-limit" => match args.next() {
+                Some(limit) => error_limit = parse_error_limit(&limit)?,
+                None => {
+                    return Err(format!(
+                        "[COMPILER] '--error-limit' expects a number\n\n{USAGE}"
+                    ))
+                }
+            },
+            flag if flag.starts_with("--error-limit=") => {
+                error_limit = parse_error_limit(&flag["--error-limit=".len()..])?
+            }
+            "--message-format" => match args.next() {
+                Some(format) => message_format = MessageFormat::from_str(&format)?,
+                None => {
+                    return Err(format!(
+                        "[COMPILER] '--message-format' expects a format\n\n{USAGE}"
+                    ))
+                }
+            },
+            flag if flag.starts_with("--message-format=") => {
+                message_format = MessageFormat::from_str(&flag["--message-format=".len()..])?
+            }
+            "-o" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => return Err(format!("[COMPILER] '-o' expects a path\n\n{USAGE}")),
+            },
+            "-" if input.is_none() => input = Some(Input::Stdin),
+            flag if flag.starts_with('-') => {
+                return Err(format!("[COMPILER] Unknown option '{flag}'\n\n{USAGE}"))
+            }
+            path if input.is_none() => input = Some(Input::File(PathBuf::from(path))),
+            path => {
+                return Err(format!(
+                    "[COMPILER] Unexpected argument '{path}', only one input allowed"
+                ))
+            }
+        }
+    }
+
+    let input = match input {
+        Some(Input::File(path)) => match path.extension().and_then(|ext| ext.to_str()) {
+            Some(SOURCE_EXT) => Input::File(path),
+            _ => {
+                return Err(format!(
+                    "[COMPILER] Invalid file extension for '{}', '.{SOURCE_EXT}' only",
+                    path.display()
+                ))
+            }
+        },
+        Some(Input::Stdin) => Input::Stdin,
+        None => return Err(format!("[COMPILER] No file path given!\n\n{USAGE}")),
     };
+    if run && emit != Emit::Exe {
+        return Err(format!(
+            "[COMPILER] '--run' needs an executable, found '--emit={emit:?}'"
+        ));
+    }
+
+    Ok(Some(Options {
+        input,
+        output,
+        emit,
+        run,
+        unicode_idents,
+        negative_whitespace,
+        no_uppercase,
+        error_limit,
+        message_format,
+    }))
+}
+
+fn parse_error_limit(limit: &str) -> Result<usize, String> {
+    limit
+        .parse()
+        .map_err(|_| format!("[COMPILER] Invalid error limit '{limit}', expected a number"))
+}
+
+/*----------------------------------------------------------------------------------------
+---- Stuff -------------------------------------------------------------------------------
+----------------------------------------------------------------------------------------*/
+
+// 'None' or '-' writes to stdout, so any stage can be piped.
+fn write_output(path: Option<&Path>, contents: &str) -> Result<(), String> {
+    match path {
+        Some(path) if path != Path::new("-") => fs::write(path, contents)
+            .map_err(|e| format!("[COMPILER] Unable to write '{}': {e}", path.display())),
+        _ => io::stdout()
+            .write_all(contents.as_bytes())
+            .map_err(|e| format!("[COMPILER] Unable to write to stdout: {e}")),
+    }
 }
 
 /*----------------------------------------------------------------------------------------
 ---- Misc --------------------------------------------------------------------------------
 ----------------------------------------------------------------------------------------*/
 
-fn print_tokens(tokens: &VecDeque<Token>) {
+// Every error up to the limit, then a summary of how many there were.
+// .. warnings follow the errors, they're left out when the errors were cut short.
+fn fmt_diagnostics(
+    diagnostics: &[Diagnostic],
+    sources: &SourceMap,
+    color: bool,
+    limit: usize,
+) -> String {
+    let (errors, warnings) = split_diagnostics(diagnostics, limit);
+    let mut str = String::new();
+    for e in errors.iter().chain(&warnings) {
+        str += &e.render(sources, color);
+        str += "\n";
+    }
+
+    let error_count = diagnostics
+        .iter()
+        .filter(|e| e.severity == Severity::Error)
+        .count();
+    match error_count {
+        count if count > errors.len() => {
+            str += &format!(
+                "[COMPILER] Stopped after {} errors, {} more not shown (--error-limit)",
+                errors.len(),
+                count - errors.len()
+            )
+        }
+        1 => str += "[COMPILER] Aborting due to the previous error",
+        count => str += &format!("[COMPILER] Aborting due to {count} previous errors"),
+    }
+    str
+}
+
+// One JSON object per line, without the summary.
+fn fmt_diagnostics_json(diagnostics: &[Diagnostic], sources: &SourceMap, limit: usize) -> String {
+    let (errors, warnings) = split_diagnostics(diagnostics, limit);
+    let json: Vec<String> = errors
+        .iter()
+        .chain(&warnings)
+        .map(|e| e.to_json(sources))
+        .collect();
+    json.join("\n")
+}
+
+// The errors to show, up to the limit, and the warnings to show after them.
+fn split_diagnostics(
+    diagnostics: &[Diagnostic],
+    limit: usize,
+) -> (Vec<&Diagnostic>, Vec<&Diagnostic>) {
+    let limit = match limit {
+        0 => usize::MAX,
+        limit => limit,
+    };
+    let (errors, warnings): (Vec<&Diagnostic>, Vec<&Diagnostic>) = diagnostics
+        .iter()
+        .partition(|e| e.severity == Severity::Error);
+    match errors.len() > limit {
+        true => (errors.into_iter().take(limit).collect(), Vec::new()),
+        false => (errors, warnings),
+    }
+}
+
+// 'Token { Ident('x') | 3:9 }', the span as a line and column.
+fn fmt_tokens(tokens: &VecDeque<Token>, sources: &SourceMap) -> String {
     fn fmt_123(tok: &Token) -> String {
-        match tok.as_str() {
-            // Some(val) => match tok.kind {
-            //     TokenKind::Ident => format!("{:?}('{val}')", tok.kind),
-            //     _ => format!("{:?}({val})", tok.kind),
-            // },
-            // None => format!("{:?}", tok.kind),
-            "" => format!("{:?}", tok.kind),
-            val @ _ => match tok.kind {
+        match tok.value {
+            None => format!("{:?}", tok.kind),
+            Some(ref val) => match tok.kind {
                 TokenKind::Ident => format!("{:?}('{val}')", tok.kind),
+                TokenKind::StrLit => format!("{:?}({val:?})", tok.kind),
+                TokenKind::DocComment => format!("{:?}({val:?})", tok.kind),
+                TokenKind::CharLit => format!("{:?}('{}')", tok.kind, val.escape_default()),
                 _ => format!("{:?}({val})", tok.kind),
             },
         }
     }
 
+    let line_col = |tok: &Token| sources.line_col(tok.span.file_id, tok.span.lo);
     let mut val_max_len = 0;
-    let mut x_max_len = 0;
-    let mut y_max_len = 0;
+    let mut line_max_len = 0;
+    let mut col_max_len = 0;
     for tok in tokens {
-        let val_cur_len = fmt_123(tok).len();
+        let val_cur_len = fmt_123(tok).chars().count();
         val_max_len = max(val_max_len, val_cur_len);
 
-        let (x, y) = tok.pos;
-        x_max_len = max(x_max_len, format!("{x}").len());
-        y_max_len = max(y_max_len, format!("{y}").len());
+        let (line, col) = line_col(tok);
+        line_max_len = max(line_max_len, format!("{line}").len());
+        col_max_len = max(col_max_len, format!("{col}").len());
     }
 
+    let mut str = String::new();
     for tok in tokens {
         let val_str = fmt_123(tok);
-        let val_whitespace = " ".repeat(val_max_len - val_str.len());
-        let x_str = format!("{x:?}", x = tok.pos.0);
-        let x_whitespace = " ".repeat(x_max_len - x_str.len());
-        let y_str = format!("{y:?}", y = tok.pos.1);
-        let y_whitespace = " ".repeat(y_max_len - y_str.len());
-        println!(
-            "Token {{ {val_str}{val_whitespace} | {x_whitespace}{x_str}, {y_str}{y_whitespace} }}"
-        )
-    }
-}
-
-fn get_file_name() -> String {
-    let args: String = env::args().skip(1).take(1).collect();
-    let file_name = args.split('.').take(1).collect::<String>();
-    let extension = args.split('.').skip(1).take(1).collect::<String>();
-    if args.is_empty() {
-        panic!("[COMPILER] No file path given!\n");
-    } else if extension != "txt" {
-        panic!("[COMPILER] Invalid file extension, '.txt' only\n")
-    }
-    file_name
-}
-
-fn get_file_contents(file_name: &str) -> Vec<String> {
-    let file = fs::File::open(format!("./examples/{file_name}.txt"))
-        .unwrap_or_else(|_| panic!("[COMPILER] Error opening file '{file_name}'\n"));
-    BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap() + "\n")
-        .collect()
-}
-
-// UBUNTU bash script:
-// read file
-// sudo nasm -felf64 $file.asm -o $file.o
-// sudo ld $file.o -o $file
-// ./$file
-// echo $?
-
-// Usage:
-// bash gen.sh
-// FILE_NAME
+        let val_whitespace = " ".repeat(val_max_len - val_str.chars().count());
+        let (line, col) = line_col(tok);
+        let line_str = format!("{line}");
+        let line_whitespace = " ".repeat(line_max_len - line_str.len());
+        let col_str = format!("{col}");
+        let col_whitespace = " ".repeat(col_max_len - col_str.len());
+        str += &format!(
+            "Token {{ {val_str}{val_whitespace} | {line_whitespace}{line_str}:{col_str}{col_whitespace} }}\n"
+        );
+    }
+    str
+}
+
+// top level statements, then function bodies (only their signature is left in the ast).
+fn fmt_checked_ast(checker: &Checker) -> String {
+    let mut str = format!("{:#?}\n", checker.ast);
+    let mut signatures: Vec<&String> = checker.fn_map.keys().collect();
+    signatures.sort();
+    for signature in signatures {
+        str += &format!("{:#?}\n", checker.fn_map.get(signature).unwrap());
+    }
+    str
+}
+
+fn get_file_contents(input: &Input) -> Result<String, String> {
+    let mut reader: Box<dyn Read> = match input {
+        Input::File(path) => match fs::File::open(path) {
+            Ok(file) => Box::new(file),
+            Err(e) => {
+                return Err(format!(
+                    "[COMPILER] Error opening file '{}': {e}",
+                    path.display()
+                ))
+            }
+        },
+        Input::Stdin => Box::new(io::stdin()),
+    };
+    let mut contents = String::new();
+    match reader.read_to_string(&mut contents) {
+        Ok(_) => Ok(contents),
+        Err(e) => Err(format!("[COMPILER] Error reading input: {e}")),
+    }
+}
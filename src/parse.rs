@@ -2,15 +2,20 @@
 //  PARSE_TYPE:
 //      - to handle generic types, e.g Vec<u16>
 use crate::{
-    debug, debugln, err,
+    debug, debugln,
+    diagnostic::Diagnostic,
+    err,
     lex::{Associativity, Token, TokenFlags, TokenKind},
     semantic::{AddressingMode, ExprData, InitExpr, SemVariable},
+    span::Span,
 };
 use std::collections::VecDeque;
 
 const LOG_DEBUG_INFO: bool = false;
-const MSG: &'static str = "PARSE";
+const CODE: u16 = 200;
+const MSG: &str = "PARSE";
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AST {
     pub stmts: Vec<NodeStmt>,
@@ -20,6 +25,7 @@ pub struct AST {
 pub struct NodeScope {
     pub stmts: Vec<NodeStmt>,
     pub inherits_stmts: bool,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -30,6 +36,16 @@ pub struct Arg {
     pub addr_mode: AddressingMode,
 }
 
+// 'start..end' or 'start..=end', with an optional 'step', e.g '0..10 step 2'.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Range {
+    pub start: NodeExpr,
+    pub end: NodeExpr,
+    pub inclusive: bool,
+    pub step: Option<NodeExpr>,
+}
+
+// every node has the span of the source it was parsed from, statements exclude their ';'.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum NodeStmt {
     FnDecl {
@@ -38,6 +54,8 @@ pub enum NodeStmt {
         scope: NodeScope,
         return_type_tok: Option<Token>,
         return_addr_mode: Option<AddressingMode>,
+        doc: Option<String>,
+        span: Span,
     },
     VarDecl {
         init_expr: InitExpr,
@@ -45,88 +63,303 @@ pub enum NodeStmt {
         type_tok: Token,
         type_addr_mode: AddressingMode,
         mutable: bool,
+        doc: Option<String>,
+        span: Span,
     },
     If {
         condition: NodeExpr,
         scope: NodeScope,
         branches: Vec<NodeStmt>,
+        span: Span,
     },
     ElseIf {
         condition: NodeExpr,
         scope: NodeScope,
+        span: Span,
     },
     Else(NodeScope),
     While {
+        label: Option<Token>,
         condition: NodeExpr,
         scope: NodeScope,
+        span: Span,
     },
-    Assign {
+    For {
+        label: Option<Token>,
         ident: Token,
+        type_tok: Option<Token>, // inferred from the range when missing
+        range: Range,
+        scope: NodeScope,
+        span: Span,
+    },
+    Assign {
+        target: NodeExpr, // a variable or a dereference, 'x' or 'p^'
+        expr: NodeExpr,
+        span: Span,
+    },
+    // 'x += e' is 'x = x + (e)', the op is the arithmetic or bitwise counterpart.
+    CompoundAssign {
+        target: NodeExpr,
+        op: TokenKind,
         expr: NodeExpr,
+        span: Span,
+    },
+    Exit {
+        expr: NodeExpr,
+        span: Span,
     },
-    Exit(NodeExpr),
     NakedScope(NodeScope),
-    Break,
-    Return(Option<NodeExpr>),
+    // an expression on its own, run for its effects: 'do_work(1, 2);'
+    Expr {
+        expr: NodeExpr,
+        span: Span,
+    },
+    // '_ = f();' throws the result away on purpose.
+    Discard {
+        expr: NodeExpr,
+        span: Span,
+    },
+    Break {
+        label: Option<Token>, // the innermost loop when missing
+        span: Span,
+    },
+    Continue {
+        label: Option<Token>,
+        span: Span,
+    },
+    Return {
+        expr: Option<NodeExpr>,
+        span: Span,
+    },
     // SEMANTIC STMT "CONVERSIONS"
     VarSemantics(SemVariable),
+    ForSemantics {
+        label: Option<Token>,
+        var: SemVariable, // scoped to the body
+        range: Range,
+        scope: NodeScope,
+        span: Span,
+    },
     FnSemantics {
         signature: String,
+        span: Span,
     },
     ReturnSemantics {
-        expr: Option<ExprData>,
+        expr: Option<NodeExpr>,
+        span: Span,
     },
 }
 
+impl NodeStmt {
+    // for error messages, instead of dumping the whole statement.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeStmt::FnDecl { .. } => "fn",
+            NodeStmt::VarDecl { .. } => "let",
+            NodeStmt::If { .. } => "if",
+            NodeStmt::ElseIf { .. } => "else if",
+            NodeStmt::Else(_) => "else",
+            NodeStmt::While { .. } => "while",
+            NodeStmt::For { .. } => "for",
+            NodeStmt::Assign { .. } => "assignment",
+            NodeStmt::CompoundAssign { .. } => "compound assignment",
+            NodeStmt::Exit { .. } => "exit",
+            NodeStmt::NakedScope(_) => "scope",
+            NodeStmt::Expr { .. } => "expression",
+            NodeStmt::Discard { .. } => "discard",
+            NodeStmt::Break { .. } => "break",
+            NodeStmt::Continue { .. } => "continue",
+            NodeStmt::Return { .. } => "return",
+            NodeStmt::VarSemantics(_) => "variable semantics",
+            NodeStmt::ForSemantics { .. } => "for semantics",
+            NodeStmt::FnSemantics { .. } => "function semantics",
+            NodeStmt::ReturnSemantics { .. } => "return semantics",
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            NodeStmt::FnDecl { span, .. }
+            | NodeStmt::VarDecl { span, .. }
+            | NodeStmt::If { span, .. }
+            | NodeStmt::ElseIf { span, .. }
+            | NodeStmt::While { span, .. }
+            | NodeStmt::For { span, .. }
+            | NodeStmt::ForSemantics { span, .. }
+            | NodeStmt::Assign { span, .. }
+            | NodeStmt::CompoundAssign { span, .. }
+            | NodeStmt::Exit { span, .. }
+            | NodeStmt::Expr { span, .. }
+            | NodeStmt::Discard { span, .. }
+            | NodeStmt::Break { span, .. }
+            | NodeStmt::Continue { span, .. }
+            | NodeStmt::Return { span, .. }
+            | NodeStmt::FnSemantics { span, .. }
+            | NodeStmt::ReturnSemantics { span, .. } => *span,
+            NodeStmt::Else(scope) | NodeStmt::NakedScope(scope) => scope.span,
+            NodeStmt::VarSemantics(var) => var.ident.span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum NodeExpr {
     BinaryExpr {
         op: TokenKind,
         lhs: Box<NodeExpr>,
         rhs: Box<NodeExpr>,
+        span: Span,
     },
     UnaryExpr {
         op: TokenKind,
         operand: Box<NodeExpr>,
+        span: Span,
+    },
+    Cast {
+        expr: Box<NodeExpr>,
+        type_tok: Token,
+        span: Span,
     },
     Term(NodeTerm),
 }
 
+impl NodeExpr {
+    pub fn span(&self) -> Span {
+        match self {
+            NodeExpr::BinaryExpr { span, .. }
+            | NodeExpr::UnaryExpr { span, .. }
+            | NodeExpr::Cast { span, .. } => *span,
+            NodeExpr::Term(term) => term.span(),
+        }
+    }
+
+    // makes no calls, evaluating it has no effect besides its value.
+    pub fn is_pure(&self) -> bool {
+        match self {
+            NodeExpr::BinaryExpr { lhs, rhs, .. } => lhs.is_pure() && rhs.is_pure(),
+            NodeExpr::UnaryExpr { operand, .. } => operand.is_pure(),
+            NodeExpr::Cast { expr, .. } => expr.is_pure(),
+            NodeExpr::Term(NodeTerm::FnCall { .. }) => false,
+            NodeExpr::Term(_) => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum NodeTerm {
-    True,
-    False,
+    True(Span),
+    False(Span),
     Ident(Token),
     IntLit(Token),
-    FnCall { ident: Token, args: Vec<NodeExpr> },
+    FloatLit(Token),
+    CharLit(Token),
+    FnCall {
+        ident: Token,
+        args: Vec<NodeExpr>,
+        span: Span,
+        signature: Option<String>, // the overload it resolved to, filled in by the checker.
+    },
 }
 
-pub struct Parser {
-    pub tokens: VecDeque<Token>,
-    pub idx: usize,
-    pub pos: (u32, u32),
+impl NodeTerm {
+    pub fn span(&self) -> Span {
+        match self {
+            NodeTerm::True(span) | NodeTerm::False(span) | NodeTerm::FnCall { span, .. } => *span,
+            NodeTerm::Ident(tok)
+            | NodeTerm::IntLit(tok)
+            | NodeTerm::FloatLit(tok)
+            | NodeTerm::CharLit(tok) => tok.span,
+        }
+    }
+}
+
+// Pulls tokens as it goes, e.g straight from a 'Lexer', only the few it peeks at are buffered.
+pub struct Parser<'a> {
+    tokens: Box<dyn Iterator<Item = Token> + 'a>,
+    lookahead: VecDeque<Token>, // peeked at, not consumed yet
+    consumed: usize,
+    prev_span: Span, // of the last consumed token
+    errors: Vec<Diagnostic>,
+    lex_error: bool, // consumed an 'Error' token, the lexer already reported it
 }
 
-impl Parser {
-    pub fn new(input: VecDeque<Token>) -> Parser {
+impl<'a> Parser<'a> {
+    pub fn new(tokens: impl IntoIterator<Item = Token> + 'a) -> Parser<'a> {
+        // a lossless stream's 'Eof' only carries trivia.
+        let tokens = tokens.into_iter().filter(|tok| tok.kind != TokenKind::Eof);
         Parser {
-            tokens: input,
-            idx: 0,
-            pos: (0, 0),
+            tokens: Box::new(tokens),
+            lookahead: VecDeque::new(),
+            consumed: 0,
+            prev_span: Span::default(),
+            errors: Vec::new(),
+            lex_error: false,
         }
     }
 
-    pub fn parse_ast(&mut self) -> Result<AST, String> {
+    // Broken statements are reported and skipped, so every independent error is found in one go.
+    pub fn parse_ast(&mut self) -> Result<AST, Vec<Diagnostic>> {
         let mut ast: AST = AST { stmts: Vec::new() };
-        while let Some(_) = self.peek(0) {
-            ast.stmts.push(self.parse_top_level()?);
+        while self.peek(0).is_some() {
+            let start = self.consumed;
+            self.lex_error = false;
+            match self.parse_top_level() {
+                Ok(stmt) => ast.stmts.push(stmt),
+                Err(e) => {
+                    self.recover(start);
+                    self.report(e);
+                }
+            }
+        }
+
+        match self.errors.is_empty() {
+            true => Ok(ast),
+            false => Err(std::mem::take(&mut self.errors)),
         }
-        Ok(ast)
     }
 
-    fn parse_top_level(&mut self) -> Result<NodeStmt, String> {
-        match self.peek(0) {
-            Some(tok) if tok.kind != TokenKind::Fn => {
+    // A statement broken by an 'Error' token only repeats the lexer's error, drop it.
+    // .. call after 'recover', which consumes the 'Error' token.
+    fn report(&mut self, e: Diagnostic) {
+        if !self.lex_error {
+            self.errors.push(e);
+        }
+    }
+
+    // Skips the rest of a broken statement, up to a statement boundary:
+    //  - past its ';', or the '}' ending a scope it opened.
+    //  - up to the next 'fn', or the '}' closing the enclosing scope (left for parse_scope).
+    // always consumes a token when the statement didn't, otherwise it'd fail forever.
+    fn recover(&mut self, start: usize) {
+        let mut depth = 0;
+        while let Some(kind) = self.peek(0).map(|tok| tok.kind) {
+            let progress = self.consumed > start;
+            match kind {
+                TokenKind::Fn | TokenKind::CloseBrace if depth == 0 && progress => return,
+                TokenKind::SemiColon if depth == 0 => {
+                    self.consume();
+                    return;
+                }
+                TokenKind::CloseBrace if depth <= 1 => {
+                    self.consume();
+                    return;
+                }
+                TokenKind::OpenBrace => depth += 1,
+                TokenKind::CloseBrace => depth -= 1,
+                _ => (),
+            }
+            self.consume();
+        }
+    }
+
+    fn parse_top_level(&mut self) -> Result<NodeStmt, Diagnostic> {
+        // look past any doc comments, they belong to the statement after them.
+        let mut offset = 0;
+        while self.token_equals(TokenKind::DocComment, offset).is_ok() {
+            offset += 1;
+        }
+        match self.peek(offset).map(|tok| tok.kind) {
+            Some(kind) if kind != TokenKind::Fn => {
                 return self.parse_stmt();
                 // err!(
                 //     self,
@@ -134,19 +367,18 @@ impl Parser {
                 // )
             }
             Some(_) => (),
-            None => return err!(self, "No token to parse"),
+            None if self.peek(0).is_some() => return self.parse_stmt(),
+            None => return err!(self.eof_span(), "No token to parse"),
         };
 
-        self.expect(TokenKind::Fn)?;
+        let doc = self.parse_doc();
+        let start = self.expect(TokenKind::Fn)?.span;
         let ident = self.expect(TokenKind::Ident)?;
         self.expect(TokenKind::OpenParen)?;
 
+        // 'a: i32, b: i32', a trailing ',' is allowed.
         let mut args = Vec::new();
         while self.token_equals(TokenKind::CloseParen, 0).is_err() {
-            if args.len() > 0 {
-                self.expect(TokenKind::Comma)?;
-            }
-
             let mutable = self.expect(TokenKind::Mut).is_ok();
             let ident = self.expect(TokenKind::Ident)?;
             self.expect(TokenKind::Colon)?;
@@ -157,6 +389,10 @@ impl Parser {
                 type_tok,
                 addr_mode,
             });
+            if self.expect(TokenKind::Comma).is_err() {
+                self.expect_list_end("parameters", "fn f(a: i32, b: i32)")?;
+                break;
+            }
         }
         self.expect(TokenKind::CloseParen)?;
 
@@ -172,18 +408,50 @@ impl Parser {
         Ok(NodeStmt::FnDecl {
             ident,
             args,
+            span: start.to(scope.span),
             scope,
             return_type_tok,
             return_addr_mode,
+            doc,
         })
     }
 
-    fn parse_stmt(&mut self) -> Result<NodeStmt, String> {
+    // '///' lines are joined into the doc of the 'fn' or 'let' after them.
+    // .. docs on anything else are reported, the statement is still parsed.
+    fn parse_doc(&mut self) -> Option<String> {
+        let mut lines = Vec::new();
+        let mut span: Option<Span> = None;
+        while let Ok(tok) = self.expect(TokenKind::DocComment) {
+            span = Some(span.map_or(tok.span, |span| span.to(tok.span)));
+            lines.push(tok.value.unwrap_or_default());
+        }
+
+        let span = span?;
+        match self.peek(0) {
+            Some(tok) if matches!(tok.kind, TokenKind::Fn | TokenKind::Let) => {
+                Some(lines.join("\n"))
+            }
+            _ => {
+                let e = Diagnostic::error(
+                    CODE,
+                    "Doc comment must be followed by a 'fn' or 'let'".to_string(),
+                )
+                .with_span(span)
+                .with_help("use '//' for a regular comment");
+                self.errors.push(e);
+                None
+            }
+        }
+    }
+
+    fn parse_stmt(&mut self) -> Result<NodeStmt, Diagnostic> {
+        let doc = self.parse_doc();
         let tok = match self.peek(0) {
             Some(tok) => tok,
-            None => return err!(self, "No statement to parse"),
+            None => return err!(self.eof_span(), "No statement to parse"),
         };
-        debugln!(self, "parsing statement: {tok:?}");
+        debugln!("parsing statement: {tok:?}");
+        let start = tok.span;
 
         let stmt = match tok.kind {
             TokenKind::Let => {
@@ -205,6 +473,8 @@ impl Parser {
                     type_tok,
                     type_addr_mode,
                     mutable,
+                    doc,
+                    span: start.to(self.prev_span),
                 }
             }
             TokenKind::If => {
@@ -213,13 +483,15 @@ impl Parser {
                 let scope = self.parse_scope()?;
 
                 let mut branches = Vec::new();
-                loop {
-                    if self.expect(TokenKind::Else).is_err() {
-                        break;
-                    } else if self.expect(TokenKind::If).is_ok() {
+                while let Ok(else_tok) = self.expect(TokenKind::Else) {
+                    let else_start = else_tok.span;
+                    if self.expect(TokenKind::If).is_ok() {
+                        let condition = self.parse_expr(0)?;
+                        let scope = self.parse_scope()?;
                         branches.push(NodeStmt::ElseIf {
-                            condition: self.parse_expr(0)?,
-                            scope: self.parse_scope()?,
+                            condition,
+                            span: else_start.to(scope.span),
+                            scope,
                         });
                         continue;
                     }
@@ -231,101 +503,303 @@ impl Parser {
                     condition,
                     scope,
                     branches,
+                    span: start.to(self.prev_span),
                 }
             }
             TokenKind::Fn => {
                 return err!(
-                    self,
+                    start,
                     "Functions cannot be nested, they're top level statements"
                 )
             }
             TokenKind::Return => {
                 self.expect(TokenKind::Return)?;
-                match self.peek(0) {
-                    Some(tok) if tok.kind == TokenKind::SemiColon => NodeStmt::Return(None),
-                    _ => NodeStmt::Return(Some(self.parse_expr(0)?)),
+                let expr = match self.peek(0) {
+                    Some(tok) if tok.kind == TokenKind::SemiColon => None,
+                    _ => Some(self.parse_expr(0)?),
+                };
+                NodeStmt::Return {
+                    expr,
+                    span: start.to(self.prev_span),
                 }
             }
-            TokenKind::While => {
-                self.expect(TokenKind::While)?;
-                let condition = self.parse_expr(0)?;
-                let scope = self.parse_scope()?;
-                NodeStmt::While { condition, scope }
-            }
-            TokenKind::Ident => {
-                let ident = self.expect(TokenKind::Ident)?;
-                match self.peek(0) {
-                    // Assignment: consume ident & '='. parse expr.
-                    Some(tok) if tok.kind == TokenKind::Eq => {
-                        self.expect(TokenKind::Eq)?;
-                        NodeStmt::Assign {
-                            ident,
-                            expr: self.parse_expr(0)?,
-                        }
-                    }
-                    // Compound Assign: clone ident, swap assign to arith counterpart, parse expr
-                    //      - 'ident += 5;' => 'ident + 5;'
-                    Some(tok) if tok.kind.has_flags(TokenFlags::ASSIGN) => {
-                        self.tokens.push_front(ident.clone()); // TODO(TOM): this may not work !
-                        let comp_assign = self.peek_mut(1).unwrap();
-                        comp_assign.kind = comp_assign.kind.assign_to_arithmetic()?;
-                        NodeStmt::Assign {
-                            ident,
-                            expr: self.parse_expr(0)?,
-                        }
+            TokenKind::While => self.parse_while(None, start)?,
+            TokenKind::For => self.parse_for(None, start)?,
+            // "'outer: while ..", names the loop for a 'break' or 'continue' nested deeper.
+            TokenKind::Label => {
+                let label = self.expect(TokenKind::Label)?;
+                self.expect(TokenKind::Colon)?;
+                match self.peek(0).map(|tok| tok.kind) {
+                    Some(TokenKind::While) => self.parse_while(Some(label), start)?,
+                    Some(TokenKind::For) => self.parse_for(Some(label), start)?,
+                    _ => {
+                        let name = label.as_str();
+                        return Err(Diagnostic::error(
+                            CODE,
+                            format!("Expected a loop after the label {name}"),
+                        )
+                        .with_span(label.span)
+                        .with_help("only 'while' and 'for' loops can be labeled"));
                     }
-                    _ => return err!(self, "Naked Expression => '{:?}', Not Valid", self.peek(0)),
+                }
+            }
+            TokenKind::Ident
+            | TokenKind::IntLit
+            | TokenKind::FloatLit
+            | TokenKind::CharLit
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::OpenParen => self.parse_expr_stmt(start)?,
+            kind if kind.has_flags(TokenFlags::UNARY) => self.parse_expr_stmt(start)?,
+            TokenKind::Underscore => {
+                self.expect(TokenKind::Underscore)?;
+                self.expect(TokenKind::Eq)
+                    .map_err(|e| e.with_help("'_' only discards a result, e.g '_ = f();'"))?;
+                let expr = self.parse_expr(0)?;
+                NodeStmt::Discard {
+                    span: start.to(expr.span()),
+                    expr,
                 }
             }
             TokenKind::Exit => {
                 self.expect(TokenKind::Exit)?;
                 self.token_equals(TokenKind::OpenParen, 0)?;
                 let expr = self.parse_expr(0)?;
-                NodeStmt::Exit(expr)
+                NodeStmt::Exit {
+                    span: start.to(expr.span()),
+                    expr,
+                }
             }
             TokenKind::Break => {
                 self.expect(TokenKind::Break)?;
-                NodeStmt::Break
+                let label = self.expect(TokenKind::Label).ok();
+                NodeStmt::Break {
+                    label,
+                    span: start.to(self.prev_span),
+                }
+            }
+            TokenKind::Continue => {
+                self.expect(TokenKind::Continue)?;
+                let label = self.expect(TokenKind::Label).ok();
+                NodeStmt::Continue {
+                    label,
+                    span: start.to(self.prev_span),
+                }
             }
             TokenKind::OpenBrace => NodeStmt::NakedScope(self.parse_scope()?),
-            _ => return err!(self, "Invalid Statement =>\n{tok:#?}"),
+            _ => {
+                let kind = tok.kind;
+                return err!(start, "Invalid Statement, found '{kind:?}'");
+            }
         };
 
         // statments that do/don't require a ';' to end.
         match stmt {
-            NodeStmt::Exit(_)
+            NodeStmt::Exit { .. }
             | NodeStmt::Assign { .. }
+            | NodeStmt::CompoundAssign { .. }
+            | NodeStmt::Expr { .. }
+            | NodeStmt::Discard { .. }
             | NodeStmt::VarDecl { .. }
-            | NodeStmt::Break
-            | NodeStmt::Return(_) => match self.expect(TokenKind::SemiColon) {
-                Ok(_) => Ok(stmt),
-                Err(e) => err!("{e}.\n{stmt:#?}"),
-            },
+            | NodeStmt::Break { .. }
+            | NodeStmt::Continue { .. }
+            | NodeStmt::Return { .. } => {
+                // nothing wrong with the statement itself, carry on as if the ';' was there.
+                // .. point just after it, where the ';' should be.
+                if let Err(e) = self.expect(TokenKind::SemiColon) {
+                    let note = format!("'{}' statement not ended", stmt.name());
+                    let found = e.span.unwrap_or(self.eof_span());
+                    let e = self
+                        .comma_help(e)
+                        .with_span(stmt.span().shrink_to_hi())
+                        .with_label(found, "unexpected token")
+                        .with_note(note);
+                    if self.token_equals(TokenKind::Error, 0).is_err() {
+                        self.errors.push(e);
+                    }
+                }
+                Ok(stmt)
+            }
             _ => Ok(stmt),
         }
     }
 
-    fn parse_scope(&mut self) -> Result<NodeScope, String> {
+    // 'x = 5', 'x += 5', 'p^ = 5', or an expression on its own, 'do_work(1, 2)'.
+    fn parse_expr_stmt(&mut self, start: Span) -> Result<NodeStmt, Diagnostic> {
+        // the target binds tighter than any assignment, 'x *= a + b' is 'x = x * (a + b)'.
+        let target = self.parse_expr(TokenKind::Eq.get_prec_binary() + 1)?;
+        let op = match self.peek(0) {
+            Some(tok) if tok.kind.has_flags(TokenFlags::ASSIGN) => tok.kind,
+            _ => {
+                return Ok(NodeStmt::Expr {
+                    span: start.to(target.span()),
+                    expr: target,
+                })
+            }
+        };
+        self.consume();
+        self.check_target(&target)?;
+        let expr = self.parse_expr(0)?;
+        let span = start.to(expr.span());
+        Ok(match op {
+            TokenKind::Eq => NodeStmt::Assign { target, expr, span },
+            _ => NodeStmt::CompoundAssign {
+                target,
+                op: op.assign_to_arithmetic()?,
+                expr,
+                span,
+            },
+        })
+    }
+
+    // the end of a ',' separated list, which is missing its ',' if it doesn't end here.
+    fn expect_list_end(&mut self, items: &str, example: &str) -> Result<(), Diagnostic> {
+        self.token_equals(TokenKind::CloseParen, 0)
+            .map_err(|e| e.with_help(format!("{items} are separated by ',', e.g '{example}'")))
+    }
+
+    // a ',' isn't an operator, 'a, b' is only valid between parentheses of a call or a 'fn'.
+    fn comma_help(&mut self, e: Diagnostic) -> Diagnostic {
+        match self.token_equals(TokenKind::Comma, 0) {
+            Ok(_) => {
+                e.with_help("',' only separates arguments and parameters, it isn't an operator")
+            }
+            Err(_) => e,
+        }
+    }
+
+    // only a variable or a pointer variable's dereference can be assigned to, 'x' or 'p^'.
+    fn check_target(&self, target: &NodeExpr) -> Result<(), Diagnostic> {
+        match target {
+            NodeExpr::Term(NodeTerm::Ident(_)) => Ok(()),
+            NodeExpr::UnaryExpr {
+                op: TokenKind::Ptr,
+                operand,
+                ..
+            } if matches!(operand.as_ref(), NodeExpr::Term(NodeTerm::Ident(_))) => Ok(()),
+            _ => Err(
+                Diagnostic::error(CODE, "Invalid assignment target".to_string())
+                    .with_span(target.span())
+                    .with_help("assign to a variable, 'x = 5', or through a pointer, 'p^ = 5'"),
+            ),
+        }
+    }
+
+    fn parse_while(&mut self, label: Option<Token>, start: Span) -> Result<NodeStmt, Diagnostic> {
+        self.expect(TokenKind::While)?;
+        let condition = self.parse_expr(0)?;
+        let scope = self.parse_scope()?;
+        Ok(NodeStmt::While {
+            label,
+            condition,
+            span: start.to(scope.span),
+            scope,
+        })
+    }
+
+    // 'for i in 0..n', 'for i: u8 in 0..=n step 2', the range is evaluated once.
+    fn parse_for(&mut self, label: Option<Token>, start: Span) -> Result<NodeStmt, Diagnostic> {
+        self.expect(TokenKind::For)?;
+        let ident = self.expect(TokenKind::Ident)?;
+        let type_tok = match self.expect(TokenKind::Colon) {
+            Ok(_) => Some(self.expect(TokenKind::Ident)?),
+            Err(_) => None,
+        };
+        self.expect(TokenKind::In)?;
+        let range = self.parse_range()?;
+        let scope = self.parse_scope()?;
+        Ok(NodeStmt::For {
+            label,
+            ident,
+            type_tok,
+            range,
+            span: start.to(scope.span),
+            scope,
+        })
+    }
+
+    // 'step' isn't a keyword, only special after a range's end.
+    fn parse_range(&mut self) -> Result<Range, Diagnostic> {
+        let start = self.parse_expr(0)?;
+        let inclusive = match self.peek(0).map(|tok| tok.kind) {
+            Some(TokenKind::DotDot) => false,
+            Some(TokenKind::DotDotEq) => true,
+            _ => {
+                let e = self.token_equals(TokenKind::DotDot, 0).unwrap_err();
+                return Err(
+                    e.with_help("a range is 'start..end', or 'start..=end' to include 'end'")
+                );
+            }
+        };
+        self.consume();
+        let end = self.parse_expr(0)?;
+        let step = match self.peek(0) {
+            Some(tok) if tok.kind == TokenKind::Ident && tok.as_str() == "step" => {
+                self.consume();
+                Some(self.parse_expr(0)?)
+            }
+            _ => None,
+        };
+        Ok(Range {
+            start,
+            end,
+            inclusive,
+            step,
+        })
+    }
+
+    fn parse_scope(&mut self) -> Result<NodeScope, Diagnostic> {
         // consumes statements until a closebrace is found.
-        self.expect(TokenKind::OpenBrace)?;
+        let start = self.expect(TokenKind::OpenBrace)?.span;
         let mut stmts = Vec::new();
-        while self.expect(TokenKind::CloseBrace).is_err() {
-            stmts.push(self.parse_stmt()?);
+        loop {
+            match self.peek(0) {
+                Some(tok) if tok.kind == TokenKind::CloseBrace => break,
+                Some(_) => (),
+                None => {
+                    return Err(Diagnostic::error(
+                        CODE,
+                        "Unclosed scope, expected 'CloseBrace'".to_string(),
+                    )
+                    .with_span(self.eof_span())
+                    .with_label(start, "scope opened here"))
+                }
+            }
+
+            let start = self.consumed;
+            self.lex_error = false;
+            match self.parse_stmt() {
+                Ok(stmt) => stmts.push(stmt),
+                // a 'fn' can't be inside a scope, this one is probably missing its '}'.
+                // .. leave it for parse_ast to recover at.
+                Err(e) if self.token_equals(TokenKind::Fn, 0).is_ok() => return Err(e),
+                Err(e) => {
+                    self.recover(start);
+                    self.report(e);
+                }
+            }
         }
+        let end = self.expect(TokenKind::CloseBrace)?.span;
 
         Ok(NodeScope {
             stmts,
             inherits_stmts: true,
+            span: start.to(end),
         })
     }
 
-    fn parse_expr(&mut self, min_prec: i32) -> Result<NodeExpr, String> {
+    fn parse_expr(&mut self, min_prec: i32) -> Result<NodeExpr, Diagnostic> {
         let mut lhs = self.parse_term()?;
 
         loop {
             let op = match self.peek(0) {
-                Some(tok) => &tok.kind,
-                None => return err!(self, "No token to parse near =>\n{lhs:#?}"),
+                Some(tok) => tok.kind,
+                None => {
+                    return err!(
+                        self.eof_span(),
+                        "Incomplete expression, found nothing after it"
+                    )
+                }
             };
             // unary expressions don't recurse as no rhs, only iterate so
             let bin_prec = op.get_prec_binary();
@@ -334,31 +808,49 @@ impl Parser {
             // NOTE: tokens with no precedence are valued at -1, therefore always exit loop.
             // .. parse_expr escapes when it hits a semicolon because its prec is -1 !! thats unclear
             if bin_prec < min_prec && un_prec < min_prec {
-                debug!(
-                    self,
-                    "precedence climb ended: {op:?}({bin_prec}) < {min_prec}"
-                );
+                debug!("precedence climb ended: {op:?}({bin_prec}) < {min_prec}");
                 break;
             }
 
+            // 'expr as type', the rhs is a type not an expression.
+            if op == TokenKind::As {
+                self.consume();
+                let type_tok = self.expect(TokenKind::Ident)?;
+                lhs = NodeExpr::Cast {
+                    span: lhs.span().to(type_tok.span),
+                    expr: Box::new(lhs),
+                    type_tok,
+                };
+                continue;
+            }
+
             let is_unary = un_prec >= 0;
             if is_unary {
                 let tok = match self.peek(1) {
                     Some(tok) => tok,
-                    None => return err!(self, "No token to parse near =>\n{lhs:#?}"),
+                    None => {
+                        return err!(
+                            self.eof_span(),
+                            "Incomplete expression, found nothing after it"
+                        )
+                    }
                 };
                 match tok.kind {
                     // tok is an expression, must be binary
-                    TokenKind::IntLit | TokenKind::Ident | TokenKind::OpenParen => {
-                        debug!(
-                            self,
-                            "found rhs of an expression '{tok:?}', operator must not be unary!"
-                        )
+                    TokenKind::IntLit
+                    | TokenKind::FloatLit
+                    | TokenKind::CharLit
+                    | TokenKind::StrLit
+                    | TokenKind::Ident
+                    | TokenKind::OpenParen => {
+                        debug!("found rhs of an expression '{tok:?}', operator must not be unary!")
                     }
                     // not a 'NodeTerm', must be unary.
                     _ => {
+                        let op = self.consume();
                         lhs = NodeExpr::UnaryExpr {
-                            op: self.consume().kind,
+                            op: op.kind,
+                            span: lhs.span().to(op.span),
                             operand: Box::new(lhs),
                         };
                         continue;
@@ -372,64 +864,84 @@ impl Parser {
                 // Associativity::None => return err!(self, "non-associative operator => '{op:?}'"),
             };
 
+            let op = self.consume().kind;
+            let rhs = self.parse_expr(next_prec)?;
             lhs = NodeExpr::BinaryExpr {
-                op: self.consume().kind,
+                op,
+                span: lhs.span().to(rhs.span()),
                 lhs: Box::new(lhs),
-                rhs: Box::new(self.parse_expr(next_prec)?),
+                rhs: Box::new(rhs),
             }
         }
         Ok(lhs)
     }
 
     // peeking next token might not work because it could be a close paren?
-    fn parse_term(&mut self) -> Result<NodeExpr, String> {
+    fn parse_term(&mut self) -> Result<NodeExpr, Diagnostic> {
         let tok = match self.peek(0) {
             Some(_) => self.consume(),
-            None => return err!(self, "Expected term, found nothing."),
+            None => return err!(self.eof_span(), "Expected term, found nothing."),
         };
 
         match tok.kind {
-            op @ _ if op.has_flags(TokenFlags::UNARY) => {
-                debug!(self, "found unary expression: '{op:?}'");
+            op if op.has_flags(TokenFlags::UNARY) => {
+                debug!("found unary expression: '{op:?}'");
                 let operand = self.parse_expr(op.get_prec_unary() + 1)?;
                 Ok(NodeExpr::UnaryExpr {
                     op,
+                    span: tok.span.to(operand.span()),
                     operand: Box::new(operand),
                 })
             }
             TokenKind::OpenParen => {
                 // greedily consume everything in parenthesis.
                 let expr = self.parse_expr(0)?;
-                debug!(self, "parsed parens {expr:#?}");
-                self.expect(TokenKind::CloseParen)?;
+                debug!("parsed parens {expr:#?}");
+                if let Err(e) = self.expect(TokenKind::CloseParen) {
+                    return Err(self.comma_help(e));
+                }
                 Ok(expr)
             }
             TokenKind::Ident => {
                 match self.peek(0) {
                     // Function Calls
                     Some(next) if next.kind == TokenKind::OpenParen => {
+                        // 'f()', 'f(a, b)', a trailing ',' is allowed: 'f(a, b,)'.
                         self.expect(TokenKind::OpenParen)?;
                         let mut args = Vec::new();
-                        while self.expect(TokenKind::CloseParen).is_err() {
-                            if args.len() > 1 {
-                                self.expect(TokenKind::Comma)?;
-                            }
+                        while self.token_equals(TokenKind::CloseParen, 0).is_err() {
                             args.push(self.parse_expr(0)?);
+                            if self.expect(TokenKind::Comma).is_err() {
+                                self.expect_list_end("arguments", "f(a, b)")?;
+                                break;
+                            }
                         }
-                        Ok(NodeExpr::Term(NodeTerm::FnCall { ident: tok, args }))
+                        self.expect(TokenKind::CloseParen)?;
+                        Ok(NodeExpr::Term(NodeTerm::FnCall {
+                            span: tok.span.to(self.prev_span),
+                            ident: tok,
+                            args,
+                            signature: None,
+                        }))
                     }
                     Some(_) => Ok(NodeExpr::Term(NodeTerm::Ident(tok))),
-                    None => err!(self, "Incomplete expression, nothing after =>\n{tok:#?}"),
+                    None => err!(
+                        tok.span,
+                        "Incomplete expression, nothing after '{}'",
+                        tok.as_str()
+                    ),
                 }
             }
             TokenKind::IntLit => Ok(NodeExpr::Term(NodeTerm::IntLit(tok))),
-            TokenKind::True => Ok(NodeExpr::Term(NodeTerm::True)),
-            TokenKind::False => Ok(NodeExpr::Term(NodeTerm::False)),
-            _ => err!(self, "Invalid Term =>\n{tok:#?}"),
+            TokenKind::FloatLit => Ok(NodeExpr::Term(NodeTerm::FloatLit(tok))),
+            TokenKind::CharLit => Ok(NodeExpr::Term(NodeTerm::CharLit(tok))),
+            TokenKind::True => Ok(NodeExpr::Term(NodeTerm::True(tok.span))),
+            TokenKind::False => Ok(NodeExpr::Term(NodeTerm::False(tok.span))),
+            _ => err!(tok.span, "Invalid Term, found '{:?}'", tok.kind),
         }
     }
 
-    fn parse_type(&mut self) -> Result<(Token, AddressingMode), String> {
+    fn parse_type(&mut self) -> Result<(Token, AddressingMode), Diagnostic> {
         let mut addr_mode = AddressingMode::Primitive;
         if self.expect(TokenKind::Ptr).is_ok() {
             addr_mode = AddressingMode::Pointer;
@@ -440,34 +952,41 @@ impl Parser {
         Ok((type_ident, addr_mode))
     }
 
-    fn token_equals(&self, kind: TokenKind, offset: usize) -> Result<(), String> {
+    fn token_equals(&mut self, kind: TokenKind, offset: usize) -> Result<(), Diagnostic> {
         match self.peek(offset) {
             Some(tok) if tok.kind == kind => Ok(()),
-            Some(tok) => err!(self, "expected '{kind:?}', found => '{:?}'", tok.kind),
-            None => err!(self, "No token to evaluate"),
+            Some(tok) => err!(tok.span, "expected '{kind:?}', found '{:?}'", tok.kind),
+            None => err!(self.eof_span(), "expected '{kind:?}', found nothing"),
         }
     }
 
-    fn peek(&self, offset: usize) -> Option<&Token> {
-        self.tokens.get(self.idx + offset)
-    }
-
-    fn peek_mut(&mut self, offset: usize) -> Option<&mut Token> {
-        self.tokens.get_mut(self.idx + offset)
+    // Pulls tokens into the lookahead until 'offset' is in it, or the stream runs out.
+    fn peek(&mut self, offset: usize) -> Option<&Token> {
+        while self.lookahead.len() <= offset {
+            self.lookahead.push_back(self.tokens.next()?);
+        }
+        self.lookahead.get(offset)
     }
 
     fn consume(&mut self) -> Token {
-        debug!(self, "consuming: {:?}", self.peek(0).unwrap());
-        match self.tokens.pop_front() {
+        debug!("consuming: {:?}", self.peek(0).unwrap());
+        match self.lookahead.pop_front().or_else(|| self.tokens.next()) {
             Some(tok) => {
-                self.pos = tok.pos;
+                self.consumed += 1;
+                self.prev_span = tok.span;
+                self.lex_error |= tok.kind == TokenKind::Error;
                 tok
             }
-            None => err!(self, "expected token to consume, found nothing.").unwrap(),
+            None => err!("expected token to consume, found nothing.").unwrap(),
         }
     }
 
-    fn expect(&mut self, kind: TokenKind) -> Result<Token, String> {
+    // Where a missing token would be, just after the last one.
+    fn eof_span(&self) -> Span {
+        self.prev_span.shrink_to_hi()
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<Token, Diagnostic> {
         self.token_equals(kind, 0)?;
         Ok(self.consume())
     }
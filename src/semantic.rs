@@ -13,10 +13,12 @@
 //      ✅ Type Conversions
 //          - Implicit: integers being converted to a larger integer, e.g u16 = u8
 //          - Explicit: Everything else, using syntax: type_x as type_y
+//              - numbers cast to any number, 'char' only to and from 'u8'.
 //      ❌ Integer Bounds Checks
 //          - requires me to interpret every arith expression? let it be ub for now :)
 //      ✅ IntegerLitereal Coercion
 //          - its not a concrete type and can be coerced into any integer, after bounds checked.
+//          - float literals are the same for 'f32' && 'f64', but floats never mix with integers.
 //      ✅ Pointers
 //          - always have usize, not a defined type but an attribute, that modifies byte_size?
 //          - kindof its own type (set size), but loose (inherits type's attr)
@@ -57,9 +59,12 @@
 //      - e.g func123(int,bool) != func123(int). UNIQUE!
 
 use crate::{
-    debug, err,
+    debug,
+    diagnostic::Diagnostic,
+    err,
     lex::{Token, TokenFlags, TokenKind},
     parse::{NodeExpr, NodeScope, NodeStmt, NodeTerm, AST},
+    span::Span,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -68,8 +73,12 @@ use std::{
 
 pub type Byte = usize;
 const PTR_WIDTH: Byte = 8;
-const LOG_DEBUG_INFO: bool = true;
-const MSG: &'static str = "SEMANTIC";
+const LOG_DEBUG_INFO: bool = false;
+const CODE: u16 = 300;
+const MSG: &str = "SEMANTIC";
+const INT_SUFFIXES: [&str; 10] = [
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum AddressingMode {
@@ -82,7 +91,9 @@ pub enum AddressingMode {
 pub enum TypeMode {
     Void,
     Bool,
+    Char,
     IntLit,
+    FloatLit,
     Int { signed: bool },
     Float { signed: bool },
 }
@@ -153,10 +164,11 @@ pub struct SemFn {
     pub arg_semantics: Vec<SemVariable>, // treat like semantic variables ??
     pub return_type_id: Option<usize>,
     pub return_type_data: Option<ExprData>,
+    pub doc: Option<String>,
 }
 
 struct SemContext {
-    loop_count: isize, // not usize to get useful error messages in debug build, instead of oob error
+    loops: Vec<Option<Token>>, // the labels of the loops around a statement, innermost last
     cur_scope_id: usize,
     scope_inherit_bounds_id: Option<usize>,
 
@@ -170,19 +182,21 @@ struct SemContext {
 pub struct Checker {
     pub ast: AST,
     ctx: SemContext,
-    pos: (u32, u32),
     pub types: Vec<Type>,
     pub fn_map: HashMap<String, SemFn>,
     vars: Vec<SemVariable>,
     var_map: HashMap<String, usize>,
     pub type_map: HashMap<String, usize>,
+    errors: Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
 }
 
 impl Checker {
-    pub fn check_ast(ast: AST) -> Result<Checker, String> {
+    pub fn check_ast(ast: AST) -> Result<Checker, Vec<Diagnostic>> {
         let types = Vec::from([
             new_base("void", 0, TypeMode::Void),
             new_base("bool", 1, TypeMode::Bool),
+            new_base("char", 1, TypeMode::Char),
             new_base("u8", 1, TypeMode::Int { signed: false }),
             new_base("u16", 2, TypeMode::Int { signed: false }),
             new_base("u32", 4, TypeMode::Int { signed: false }),
@@ -193,13 +207,13 @@ impl Checker {
             new_base("i32", 4, TypeMode::Int { signed: true }),
             new_base("i64", PTR_WIDTH, TypeMode::Int { signed: true }),
             new_base("isize", PTR_WIDTH, TypeMode::Int { signed: true }),
-            new_base("f32", 4, TypeMode::Int { signed: true }),
-            new_base("f64", PTR_WIDTH, TypeMode::Int { signed: true }),
+            new_base("f32", 4, TypeMode::Float { signed: true }),
+            new_base("f64", PTR_WIDTH, TypeMode::Float { signed: true }),
         ]);
         let mut checker = Checker {
             ast: AST { stmts: Vec::new() },
             ctx: SemContext {
-                loop_count: 0,
+                loops: Vec::new(),
                 cur_scope_id: 0,
                 scope_inherit_bounds_id: None,
                 valid_return: false,
@@ -208,12 +222,13 @@ impl Checker {
                 return_type_id: None,
                 return_type_data: None,
             },
-            pos: (0, 0),
             vars: Vec::new(),
             fn_map: HashMap::new(),
             var_map: HashMap::new(),
             types,
             type_map: HashMap::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
         };
 
         for (n, base) in checker.types.iter().enumerate() {
@@ -224,7 +239,10 @@ impl Checker {
             stmts: Vec::with_capacity(ast.stmts.len()),
         };
         for stmt in ast.stmts {
-            sem_ast.stmts.push(checker.check_top_level(stmt)?);
+            match checker.check_top_level(stmt) {
+                Ok(stmt) => sem_ast.stmts.push(stmt),
+                Err(e) => checker.errors.push(e),
+            }
         }
         checker.ast = sem_ast;
 
@@ -235,25 +253,33 @@ impl Checker {
         //          - argv: an array of length argc+1, each pointer points to a null terminated char[]
         // Instead: use one array with a length
         // Checking for the Entry Point
-        match checker.fn_map.get("main") {
+        let entry: Result<(), Diagnostic> = match checker.fn_map.get("main") {
             Some(func) if !func.arg_semantics.is_empty() => {
+                let arg = &func.arg_semantics[0].ident;
                 err!(
-                    &checker,
-                    "The 'main' function takes no arguments =>\nremove {:#?}",
-                    func.arg_semantics
+                    arg.span,
+                    "The 'main' function takes no arguments, remove '{}'",
+                    arg.as_str()
                 )
             }
-            None => {
-                err!(
-                    &checker,
-                    "No entry point for the program found. Add a 'main' function."
-                )
+            None => err!("No entry point for the program found.")
+                .map_err(|e: Diagnostic| e.with_help("add a 'main' function: 'fn main() { .. }'")),
+            _ => Ok(()),
+        };
+        if let Err(e) = entry {
+            checker.errors.push(e);
+        }
+
+        match checker.errors.is_empty() {
+            true => Ok(checker),
+            false => {
+                checker.errors.append(&mut checker.warnings);
+                Err(checker.errors)
             }
-            _ => Ok(checker),
         }
     }
 
-    fn check_top_level(&mut self, stmt: NodeStmt) -> Result<NodeStmt, String> {
+    fn check_top_level(&mut self, stmt: NodeStmt) -> Result<NodeStmt, Diagnostic> {
         match stmt {
             NodeStmt::FnDecl {
                 ident,
@@ -261,12 +287,14 @@ impl Checker {
                 scope,
                 return_type_tok,
                 return_addr_mode,
+                doc,
+                span,
             } => {
                 // check for name collisions
                 let fn_ident = ident.as_str();
                 if self.type_map.contains_key(fn_ident) {
                     return err!(
-                        self,
+                        ident.span,
                         "Illegal Function name, Types are reserved: '{fn_ident}'"
                     );
                 }
@@ -281,27 +309,33 @@ impl Checker {
                     // need this to create signature, so can't give most accurate err msgs..
                     if arg_idents.contains(arg.ident.value.as_ref().unwrap()) {
                         return err!(
+                            arg.ident.span,
                             "Duplicate argument name: '{arg_ident}' in function {fn_ident}"
                         );
                     } else if self.var_map.contains_key(arg_ident) {
                         return err!(
-                            self,
+                            arg.ident.span,
                             "Argument name in use: {arg_ident} in function: {fn_ident}"
                         );
                     } else if self.type_map.contains_key(arg_ident) {
                         return err!(
-                            self,
+                            arg.ident.span,
                             "Illegal argument name: {arg_ident} in function: {fn_ident}, Types are reserve keywords"
                         );
                     }
 
-                    let type_id = self.get_type_id(arg.type_tok.as_str())?;
+                    let type_id = self.get_type_id(&arg.type_tok)?;
                     let type_ref = self.types.get(type_id).unwrap();
 
+                    // a pointer argument holds an address, not the type it points to.
+                    let width = match arg.addr_mode {
+                        AddressingMode::Pointer => PTR_WIDTH,
+                        _ => type_ref.width,
+                    };
                     arg_semantics.push(SemVariable {
                         ident: arg.ident,
                         mutable: arg.mutable,
-                        width: type_ref.width,
+                        width,
                         scope_id: self.ctx.cur_scope_id + 1, // haven't incremented yet, in check_scope()
                         type_id,
                         addr_mode: arg.addr_mode,
@@ -314,7 +348,7 @@ impl Checker {
                 // e.g plus5(i32,i32)
                 let signature = match ident.as_str() {
                     "main" => "main".to_owned(),
-                    name @ _ => {
+                    name => {
                         let mut str = String::new();
                         str += name;
                         str += "(";
@@ -322,23 +356,28 @@ impl Checker {
                             str += self.types.get(arg.type_id).unwrap().ident.as_str();
                             str += ",";
                         }
-                        str.pop(); // removes extra ','
+                        if !arg_semantics.is_empty() {
+                            str.pop(); // removes extra ','
+                        }
                         str + ")"
                     }
                 };
 
                 // check for name collisions with signature.
                 if self.fn_map.contains_key(signature.as_str()) {
-                    return err!(self, "Duplicate definition of a Function: '{signature}'");
+                    return err!(
+                        ident.span,
+                        "Duplicate definition of a Function: '{signature}'"
+                    );
                 }
 
                 // Set shared data, for self.check_stmt()'s
                 self.ctx.function_decl_name = Some(signature.clone());
+                self.ctx.valid_return = false;
                 self.ctx.return_type_tok = return_type_tok;
                 match self.ctx.return_type_tok {
                     Some(ref ident) => {
-                        let return_ident = ident.as_str();
-                        let return_type_id = self.get_type_id(return_ident)?;
+                        let return_type_id = self.get_type_id(ident)?;
                         let return_type = self.types.get(return_type_id).unwrap();
                         let return_type_mode = match &return_type.form {
                             TypeForm::Base { type_mode } => *type_mode,
@@ -364,8 +403,8 @@ impl Checker {
                 // Create lambda for custom scope check
                 let checked_scope;
                 let mut_self = self as *const Checker as *mut Checker;
-                let lambda = |stmts: Vec<NodeStmt>| -> Result<Vec<NodeStmt>, String> {
-                    debug!(self, "checking {signature}'s statements!");
+                let lambda = |stmts: Vec<NodeStmt>| -> Result<Vec<NodeStmt>, Diagnostic> {
+                    debug!("checking {signature}'s statements!");
                     self.ctx.scope_inherit_bounds_id = Some(self.ctx.cur_scope_id);
 
                     for arg in &arg_semantics {
@@ -375,16 +414,14 @@ impl Checker {
                         self.vars.push(arg.clone());
                     }
 
-                    let mut checked_stmts = Vec::new();
-                    for stmt in stmts {
-                        checked_stmts.push(self.check_stmt(stmt)?);
-                        debug!(self, "added {:#?}", checked_stmts.last())
-                    }
+                    let checked_stmts = self.check_stmts(stmts);
 
-                    if !self.ctx.valid_return {
-                        return err!(self, "Not all code paths return in '{signature}'");
+                    // void functions are allowed to fall off the end.
+                    if !self.ctx.valid_return && self.ctx.return_type_id.is_some() {
+                        let msg = format!("Not all code paths return in '{signature}'");
+                        let e = Diagnostic::error(CODE, msg).with_span(ident.span);
+                        self.errors.push(e);
                     }
-                    checked_stmts.reverse();
 
                     // removes args for me! (check_scope() that is)
                     Ok(checked_stmts)
@@ -404,10 +441,11 @@ impl Checker {
                         arg_semantics,
                         return_type_id: self.ctx.return_type_id,
                         return_type_data: self.ctx.return_type_data,
+                        doc,
                     },
                 );
 
-                Ok(NodeStmt::FnSemantics { signature })
+                Ok(NodeStmt::FnSemantics { signature, span })
             }
             _ => {
                 self.check_stmt(stmt)
@@ -419,7 +457,7 @@ impl Checker {
         }
     }
 
-    fn check_stmt(&mut self, stmt: NodeStmt) -> Result<NodeStmt, String> {
+    fn check_stmt(&mut self, mut stmt: NodeStmt) -> Result<NodeStmt, Diagnostic> {
         match stmt {
             NodeStmt::VarDecl {
                 init_expr,
@@ -427,16 +465,11 @@ impl Checker {
                 type_tok,
                 type_addr_mode,
                 mutable,
+                span,
+                ..
             } => {
-                // check for name collisions
-                let str = ident.as_str();
-                if self.var_map.contains_key(str) {
-                    return err!(self, "Duplicate definition of a Variable: '{str}'");
-                } else if self.type_map.contains_key(str) {
-                    return err!(self, "Illegal Variable name, Types are reserved: '{str}'");
-                }
-
-                let type_id = self.get_type_id(type_tok.as_str())?;
+                self.check_var_name(&ident)?;
+                let type_id = self.get_type_id(&type_tok)?;
                 let var_type = self.types.get(type_id).unwrap();
 
                 // change byte width if its a pointer
@@ -447,7 +480,7 @@ impl Checker {
                     AddressingMode::Array => todo!("array byte width modifications"),
                 }
 
-                let var = SemVariable {
+                let mut var = SemVariable {
                     ident,
                     mutable,
                     width,
@@ -462,8 +495,9 @@ impl Checker {
                 self.vars.push(var.clone());
 
                 // check intial expression
-                if let InitExpr::Some(ref expr) = var.init_expr {
+                if let InitExpr::Some(ref mut expr) = var.init_expr {
                     let checked = self.check_expr(expr)?;
+                    let span = expr.span();
                     let var_data = ExprData {
                         type_mode: {
                             match &self.types.get(var.type_id).unwrap().form {
@@ -477,31 +511,34 @@ impl Checker {
                             ptr: self.new_nonnull(&var)?,
                         },
                     };
-                    self.check_type_equivalence(&var_data, &checked)?;
+                    self.check_type_equivalence(&var_data, &checked, span)?;
                 }
 
                 Ok(NodeStmt::VarSemantics(var))
             }
-            NodeStmt::FnDecl { .. } => {
-                return err!(
-                    self,
+            NodeStmt::FnDecl { ident, .. } => {
+                err!(
+                    ident.span,
                     "Functions cannot be nested, they're top level statements"
                 )
             }
-            NodeStmt::Return(_) if self.ctx.function_decl_name.is_none() => {
-                err!(self, "return not expected outside a function declaration.")
+            NodeStmt::Return { span, .. } if self.ctx.function_decl_name.is_none() => {
+                err!(span, "return not expected outside a function declaration.")
             }
-            NodeStmt::Return(expr) if expr.is_some() => {
-                let expr = expr.unwrap();
-                let expr_type_data = self.check_expr(&expr)?;
+            NodeStmt::Return {
+                expr: Some(mut expr),
+                span,
+            } => {
+                let expr_type_data = self.check_expr(&mut expr)?;
 
                 // check for return mismatch with void.
                 let return_type = match self.ctx.return_type_tok {
-                    Some(ref ident) => self.types.get(self.get_type_id(ident.as_str())?).unwrap(),
+                    Some(ref ident) => self.types.get(self.get_type_id(ident)?).unwrap(),
                     None => {
                         return err!(
-                            self,
-                            "Mismatched '{signature}' return, expected 'void', found =>\n'{expr_type_data:#?}'",
+                            expr.span(),
+                            "Mismatched '{signature}' return, expected 'void', found {}",
+                            self.fmt_exprdata(&expr_type_data),
                             signature = self.ctx.function_decl_name.as_ref().unwrap(),
                         );
                     }
@@ -509,43 +546,48 @@ impl Checker {
 
                 // checked prior to "check_type_equivalence" for better err message
                 if expr_type_data.addr_mode != self.ctx.return_type_data.unwrap().addr_mode {
-                    return err!(self,"Mismatched function and return type, '{return_type:#?}'\n .. \n'{expr_type_data:#?}'");
+                    return err!(
+                        expr.span(),
+                        "Mismatched function and return type, expected {}, found {}",
+                        self.fmt_exprdata(&self.ctx.return_type_data.unwrap()),
+                        self.fmt_exprdata(&expr_type_data)
+                    );
                 }
-                self.check_type_equivalence(&self.ctx.return_type_data.unwrap(), &expr_type_data)?;
+                self.check_type_equivalence(
+                    &self.ctx.return_type_data.unwrap(),
+                    &expr_type_data,
+                    expr.span(),
+                )?;
                 self.ctx.valid_return = true;
 
                 Ok(NodeStmt::ReturnSemantics {
-                    expr: Some(expr_type_data),
+                    expr: Some(expr),
+                    span,
                 })
             }
-            NodeStmt::Return(expr) => match &self.ctx.return_type_tok {
+            NodeStmt::Return { expr: None, span } => match &self.ctx.return_type_tok {
                 Some(tok) => {
                     err!(
-                        self,
-                        "Mismatched function and return type, 'void'\n .. \n'{tok:#?}'"
+                        span,
+                        "Mismatched function and return type, expected '{}', found 'void'",
+                        tok.as_str()
                     )
                 }
                 _ => {
                     self.ctx.valid_return = true;
-                    Ok(NodeStmt::ReturnSemantics { expr: None })
+                    Ok(NodeStmt::ReturnSemantics { expr: None, span })
                 }
             },
             NodeStmt::If {
-                condition,
+                mut condition,
                 scope,
                 branches,
+                span,
             } => {
-                let checked = self.check_expr(&condition)?;
-                match checked.type_mode {
-                    TypeMode::Bool => (),
-                    _ => {
-                        return err!(
-                            self,
-                            "'If' statement condition not 'boolean'\n{condition:#?}"
-                        );
-                    }
+                // a bad condition doesn't stop the scope from being checked.
+                if let Err(e) = self.check_condition(&mut condition, "If") {
+                    self.errors.push(e);
                 }
-
                 let checked_scope = self.check_scope_default(scope)?;
                 let mut new_branches = Vec::new();
                 for branch in branches {
@@ -558,24 +600,30 @@ impl Checker {
                         condition,
                         scope: checked_scope,
                         branches: new_branches,
+                        span,
                     });
                 }
 
                 // if a return statement is present within the 'if' scope:
                 // - check for an 'else'.
                 //  - if present, a 'return' MUST be present.
-                let found_return = checked_scope.stmts.iter().rev().find(|stmt| match stmt {
-                    NodeStmt::ReturnSemantics { .. } => true,
-                    _ => false,
-                });
+                let found_return = checked_scope
+                    .stmts
+                    .iter()
+                    .rev()
+                    .find(|stmt| matches!(stmt, NodeStmt::ReturnSemantics { .. }));
 
                 if let Some(NodeStmt::Else(scope)) = new_branches.last() {
-                    let found_return_else = scope.stmts.iter().rev().find(|stmt| match stmt {
-                        NodeStmt::ReturnSemantics { .. } => true,
-                        _ => false,
-                    });
+                    let found_return_else = scope
+                        .stmts
+                        .iter()
+                        .rev()
+                        .find(|stmt| matches!(stmt, NodeStmt::ReturnSemantics { .. }));
                     if found_return.is_some() != found_return_else.is_some() {
-                        return err!(self, "An unconditional 'if' .. 'else if' statement must both return or neither:\nif: {found_return:#?}\nelse if: {found_return_else:#?}");
+                        return err!(
+                            span,
+                            "An unconditional 'if' .. 'else' statement must both return or neither"
+                        );
                     }
                 }
 
@@ -583,81 +631,234 @@ impl Checker {
                     condition,
                     scope: checked_scope,
                     branches: new_branches,
+                    span,
                 })
             }
-            NodeStmt::ElseIf { condition, scope } => {
-                let checked = self.check_expr(&condition)?;
-                match checked.type_mode {
-                    TypeMode::Bool => Ok(NodeStmt::ElseIf {
-                        condition,
-                        scope: self.check_scope_default(scope)?,
-                    }),
-                    _ => {
-                        err!(
-                            self,
-                            "'ElseIf' statement condition not 'boolean'\n{condition:#?}"
-                        )
-                    }
+            NodeStmt::ElseIf {
+                mut condition,
+                scope,
+                span,
+            } => {
+                if let Err(e) = self.check_condition(&mut condition, "ElseIf") {
+                    self.errors.push(e);
                 }
+                Ok(NodeStmt::ElseIf {
+                    condition,
+                    scope: self.check_scope_default(scope)?,
+                    span,
+                })
             }
-            NodeStmt::Else(scope) => return Ok(NodeStmt::Else(self.check_scope_default(scope)?)),
-            NodeStmt::While { condition, scope } => {
-                self.ctx.loop_count += 1;
-                self.check_expr(&condition)?;
-                let new_scope = self.check_scope_default(scope)?;
-                self.ctx.loop_count -= 1;
+            NodeStmt::Else(scope) => Ok(NodeStmt::Else(self.check_scope_default(scope)?)),
+            NodeStmt::While {
+                label,
+                mut condition,
+                scope,
+                span,
+            } => {
+                if let Err(e) = self.check_expr(&mut condition) {
+                    self.errors.push(e);
+                }
+                self.push_loop(&label)?;
+                let new_scope = self.check_scope_default(scope);
+                self.ctx.loops.pop();
 
                 Ok(NodeStmt::While {
+                    label,
                     condition,
-                    scope: new_scope,
+                    scope: new_scope?,
+                    span,
                 })
             }
-            NodeStmt::Assign {
-                ref ident,
-                ref expr,
+            NodeStmt::For {
+                label,
+                ident,
+                type_tok,
+                mut range,
+                scope,
+                span,
             } => {
-                let var = self.get_var(ident.as_str())?;
-                let var_data = self.get_exprdata(var)?;
-                if !var.mutable {
-                    // if the variable is not initialised, this is the initialisation!
-                    match var.init_expr {
-                        InitExpr::None => {
-                            let var_mut = self.get_var_mut(ident.as_str())?;
-                            var_mut.init_expr = InitExpr::Deferred
+                // the range is checked before the loop variable exists, 'for i in 0..i' is an outer 'i'.
+                let mut bounds = vec![&mut range.start, &mut range.end];
+                bounds.extend(range.step.as_mut());
+                let mut bounds_data = Vec::new();
+                for bound in bounds {
+                    let data = self.check_expr(bound)?;
+                    if !matches!(data.type_mode, TypeMode::Int { .. } | TypeMode::IntLit) {
+                        return err!(
+                            bound.span(),
+                            "'for' loops count over integers, found {}",
+                            self.fmt_exprdata(&data)
+                        );
+                    }
+                    bounds_data.push((bound.span(), data));
+                }
+
+                // typed by its annotation, or the first typed bound, or 'i32' if they're all literals.
+                self.check_var_name(&ident)?;
+                let type_id = match type_tok {
+                    Some(ref tok) => self.get_type_id(tok)?,
+                    None => match bounds_data
+                        .iter()
+                        .find_map(|(_, data)| self.infer_type_id(data))
+                    {
+                        Some(type_id) => type_id,
+                        None => *self.type_map.get("i32").unwrap(),
+                    },
+                };
+                let var_type = self.types.get(type_id).unwrap();
+                let type_mode = match var_type.form {
+                    TypeForm::Base {
+                        type_mode: type_mode @ TypeMode::Int { .. },
+                    } => type_mode,
+                    _ => {
+                        return err!(
+                            ident.span,
+                            "'for' loops count over integers, found '{}'",
+                            var_type.ident
+                        )
+                    }
+                };
+                let var_data = ExprData {
+                    type_mode,
+                    addr_mode: AddressingMode::Primitive,
+                    form: ExprForm::Expr {
+                        inherited_width: var_type.width,
+                    },
+                };
+                for (span, data) in &bounds_data {
+                    self.check_type_equivalence(&var_data, data, *span)?;
+                }
+
+                // a literal step must move the loop on, it's never negative.
+                if let Some(step) = &range.step {
+                    let is_zero = matches!(step, NodeExpr::Term(NodeTerm::IntLit(tok)) if tok.int_lit().0 == 0);
+                    let is_negative = matches!(
+                        step,
+                        NodeExpr::UnaryExpr {
+                            op: TokenKind::Sub,
+                            ..
                         }
-                        _ => return err!(self, "Re-assignment of a Constant:\n{var:#?}"),
+                    );
+                    if is_zero || is_negative {
+                        return err!(step.span(), "A 'for' loop's step must be more than 0");
                     }
                 }
+
+                // declared a scope deeper, so it's removed along with the body's variables.
+                let var = SemVariable {
+                    ident,
+                    mutable: false,
+                    width: var_type.width,
+                    scope_id: self.ctx.cur_scope_id + 1,
+                    type_id,
+                    addr_mode: AddressingMode::Primitive,
+                    init_expr: InitExpr::Deferred,
+                };
+                self.push_loop(&label)?;
+                self.var_map
+                    .insert(var.ident.as_str().to_string(), self.vars.len());
+                self.vars.push(var.clone());
+
+                let scope = self.check_scope_default(scope);
+                self.ctx.loops.pop();
+
+                Ok(NodeStmt::ForSemantics {
+                    label,
+                    var,
+                    range,
+                    scope: scope?,
+                    span,
+                })
+            }
+            NodeStmt::Assign {
+                ref mut target,
+                ref mut expr,
+                ..
+            } => {
+                let target_data = self.check_target(target, false)?;
                 let checked = self.check_expr(expr)?;
-                self.check_type_equivalence(&var_data, &checked)?;
+                self.check_type_equivalence(&target_data, &checked, expr.span())?;
                 Ok(stmt)
             }
-            NodeStmt::Exit(ref expr) => {
-                self.check_expr(&expr)?;
+            NodeStmt::CompoundAssign {
+                ref mut target,
+                op,
+                ref mut expr,
+                span,
+            } => {
+                let target_data = self.check_target(target, true)?;
+                // checked as 'target = target op (expr)', the operator's rules still apply.
+                let mut binary = NodeExpr::BinaryExpr {
+                    op,
+                    lhs: Box::new(target.clone()),
+                    rhs: Box::new(expr.clone()),
+                    span,
+                };
+                let checked = self.check_expr(&mut binary)?;
+                self.check_type_equivalence(&target_data, &checked, expr.span())?;
+                // the checked 'expr' keeps the overloads its calls resolved to.
+                if let NodeExpr::BinaryExpr { rhs, .. } = binary {
+                    *expr = *rhs;
+                }
+                Ok(stmt)
+            }
+            NodeStmt::Exit { ref mut expr, .. } => {
+                let checked = self.check_expr(expr)?;
+                if let TypeMode::Float { .. } | TypeMode::FloatLit = checked.type_mode {
+                    return err!(
+                        expr.span(),
+                        "'exit' code must be an integer, found {}",
+                        self.fmt_exprdata(&checked)
+                    );
+                }
                 Ok(stmt)
             }
             NodeStmt::NakedScope(scope) => {
                 Ok(NodeStmt::NakedScope(self.check_scope_default(scope)?))
             }
-            NodeStmt::Break => {
-                if self.ctx.loop_count <= 0 {
-                    return err!(self, "Not inside a loop! cannot break");
+            NodeStmt::Expr { ref mut expr, span } => {
+                self.check_expr(expr)?;
+                if expr.is_pure() {
+                    let e = Diagnostic::warning(
+                        CODE,
+                        "Unused result, the expression has no effect".to_string(),
+                    )
+                    .with_span(span)
+                    .with_help("use its value, or discard it on purpose: '_ = ...'");
+                    self.warnings.push(e);
                 }
                 Ok(stmt)
             }
+            NodeStmt::Discard { ref mut expr, .. } => {
+                self.check_expr(expr)?;
+                Ok(stmt)
+            }
+            NodeStmt::Break { ref label, span } => {
+                self.check_loop_jump(label, span, "break")?;
+                Ok(stmt)
+            }
+            NodeStmt::Continue { ref label, span } => {
+                self.check_loop_jump(label, span, "continue")?;
+                Ok(stmt)
+            }
             NodeStmt::VarSemantics { .. }
+            | NodeStmt::ForSemantics { .. }
             | NodeStmt::FnSemantics { .. }
             | NodeStmt::ReturnSemantics { .. } => {
-                err!(self, "Found {stmt:#?}.. shouldn't have.")
+                err!(
+                    stmt.span(),
+                    "Found '{}' statement.. shouldn't have.",
+                    stmt.name()
+                )
             }
         }
     }
 
     // 1. checks all stmts in scope
     // 2. once scope has ended, removes all variables confined to that scopes
-    fn check_scope<F>(&mut self, scope: NodeScope, func: Option<F>) -> Result<NodeScope, String>
+    fn check_scope<F>(&mut self, scope: NodeScope, func: Option<F>) -> Result<NodeScope, Diagnostic>
     where
-        F: FnMut(Vec<NodeStmt>) -> Result<Vec<NodeStmt>, String>,
+        F: FnMut(Vec<NodeStmt>) -> Result<Vec<NodeStmt>, Diagnostic>,
     {
         self.ctx.cur_scope_id += 1;
         let does_inherit = scope.inherits_stmts;
@@ -667,13 +868,7 @@ impl Checker {
 
         let stmts = match func {
             Some(mut lambda) => lambda(scope.stmts)?,
-            None => {
-                let mut stmts = Vec::new();
-                for stmt in scope.stmts {
-                    stmts.push(self.check_stmt(stmt)?);
-                }
-                stmts
-            }
+            None => self.check_stmts(scope.stmts),
         };
 
         self.ctx.cur_scope_id -= 1;
@@ -681,7 +876,7 @@ impl Checker {
             match self.vars.last() {
                 Some(var) if var.scope_id <= self.ctx.cur_scope_id => break,
                 Some(var) => {
-                    // debug!(self, "Scope ended, removing '{}'", var.ident.as_str());
+                    // debug!("Scope ended, removing '{}'", var.ident.as_str());
                     let var = self.vars.pop().unwrap(); // assign for borrow checkers sake!
                     self.var_map.remove(var.ident.as_str());
                 }
@@ -692,23 +887,37 @@ impl Checker {
         Ok(NodeScope {
             stmts,
             inherits_stmts: does_inherit,
+            span: scope.span,
         })
     }
 
+    // A failed statement is reported and dropped, the rest of them are still checked.
+    fn check_stmts(&mut self, stmts: Vec<NodeStmt>) -> Vec<NodeStmt> {
+        let mut checked_stmts = Vec::new();
+        for stmt in stmts {
+            match self.check_stmt(stmt) {
+                Ok(stmt) => checked_stmts.push(stmt),
+                Err(e) => self.errors.push(e),
+            }
+            debug!("added {:#?}", checked_stmts.last())
+        }
+        checked_stmts
+    }
+
     // Compiler doesn't understand type of 'None', so must hide away type annotations in this function.
-    fn check_scope_default(&mut self, scope: NodeScope) -> Result<NodeScope, String> {
+    fn check_scope_default(&mut self, scope: NodeScope) -> Result<NodeScope, Diagnostic> {
         self.check_scope(
             scope,
-            None::<fn(Vec<NodeStmt>) -> Result<Vec<NodeStmt>, String>>,
+            None::<fn(Vec<NodeStmt>) -> Result<Vec<NodeStmt>, Diagnostic>>,
         )
     }
 
-    fn check_expr(&self, expr: &NodeExpr) -> Result<ExprData, String> {
+    fn check_expr(&self, expr: &mut NodeExpr) -> Result<ExprData, Diagnostic> {
         match expr {
-            NodeExpr::BinaryExpr { op, lhs, rhs } => {
+            NodeExpr::BinaryExpr { op, lhs, rhs, span } => {
                 let ldata = self.check_expr(lhs)?;
                 let rdata = self.check_expr(rhs)?;
-                // debug!(self, "lhs: {ldata:#?}\nrhs: {rdata:#?}");
+                // debug!("lhs: {ldata:#?}\nrhs: {rdata:#?}");
 
                 // Binary ops allowed for primitives && pointers.
                 match ldata.addr_mode {
@@ -716,17 +925,27 @@ impl Checker {
                         AddressingMode::Primitive | AddressingMode::Pointer => (),
                         _ => {
                             return err!(
-                                self,
+                                rhs.span(),
                                 "Binary Expressions invalid for {:?}",
-                                ldata.addr_mode
+                                rdata.addr_mode
                             )
                         }
                     },
-                    _ => return err!(self, "Binary Expressions invalid for {:?}", ldata.addr_mode),
+                    _ => {
+                        return err!(
+                            lhs.span(),
+                            "Binary Expressions invalid for {:?}",
+                            ldata.addr_mode
+                        )
+                    }
                 }
 
-                let err_msg = format!("Expr of different Type! => {ldata:#?}\n.. {rdata:#?}");
-                self.check_type_mode(ldata.type_mode, rdata.type_mode, &err_msg)?;
+                let err_msg = format!(
+                    "Expr of different Type! {} .. {}",
+                    self.fmt_exprdata(&ldata),
+                    self.fmt_exprdata(&rdata)
+                );
+                self.check_type_mode(ldata.type_mode, rdata.type_mode, &err_msg, *span)?;
 
                 // cmp        type, type => bool
                 // logical    bool, bool => bool
@@ -751,36 +970,66 @@ impl Checker {
                         }),
                         _ => {
                             err!(
-                                self,
-                                "'{op:?}' requires expr to be a boolean =>\n{ldata:#?}"
+                                lhs.span(),
+                                "'{op:?}' requires expr to be a boolean, found {}",
+                                self.fmt_exprdata(&ldata)
                             )
                         }
                     },
-                    _ if op_flags.intersects(TokenFlags::ARITH | TokenFlags::BIT) => {
-                        match ldata.type_mode {
-                            TypeMode::Int { .. } | TypeMode::Float { .. } | TypeMode::IntLit => {
-                                Ok(ExprData {
-                                    type_mode: ldata.type_mode,
-                                    addr_mode: ldata.addr_mode,
-                                    form: ExprForm::Expr {
-                                        inherited_width: width,
-                                    },
-                                })
-                            }
-                            _ => {
-                                err!(self, "'{op:?}' requires expr to be an integer or float =>\n{ldata:#?}")
-                            }
+                    _ if op_flags.contains(TokenFlags::ARITH) => match ldata.type_mode {
+                        TypeMode::Float { .. } | TypeMode::FloatLit if *op == TokenKind::Mod => {
+                            err!(*span, "'%' is not supported for floats")
                         }
-                    }
+                        TypeMode::Int { .. }
+                        | TypeMode::Float { .. }
+                        | TypeMode::IntLit
+                        | TypeMode::FloatLit => Ok(ExprData {
+                            type_mode: ldata.type_mode,
+                            addr_mode: ldata.addr_mode,
+                            form: ExprForm::Expr {
+                                inherited_width: width,
+                            },
+                        }),
+                        _ => {
+                            err!(
+                                lhs.span(),
+                                "'{op:?}' requires expr to be an integer or float, found {}",
+                                self.fmt_exprdata(&ldata)
+                            )
+                        }
+                    },
+                    _ if op_flags.contains(TokenFlags::BIT) => match ldata.type_mode {
+                        TypeMode::Int { .. } | TypeMode::IntLit => Ok(ExprData {
+                            type_mode: ldata.type_mode,
+                            addr_mode: ldata.addr_mode,
+                            form: ExprForm::Expr {
+                                inherited_width: width,
+                            },
+                        }),
+                        _ => {
+                            err!(
+                                lhs.span(),
+                                "'{op:?}' requires expr to be an integer, found {}",
+                                self.fmt_exprdata(&ldata)
+                            )
+                        }
+                    },
                     _ => err!(
-                        self,
-                        "Illegal binary expression =>\n{lhs:#?}\n.. '{op:?}' ..\n{rhs:#?}"
+                        *span,
+                        "Illegal binary expression, {} '{op:?}' {}",
+                        self.fmt_exprdata(&ldata),
+                        self.fmt_exprdata(&rdata)
                     ),
                 }
             }
-            NodeExpr::UnaryExpr { op, operand } => {
-                let checked = self.check_expr(&*operand)?;
-                // debug!(self, "{checked:#?}");
+            NodeExpr::UnaryExpr { op, operand, span } => {
+                let checked = match operand.as_ref() {
+                    NodeExpr::Term(NodeTerm::IntLit(tok)) if *op == TokenKind::Sub => {
+                        self.check_int_lit(tok, true)?
+                    }
+                    _ => self.check_expr(operand)?,
+                };
+                // debug!("{checked:#?}");
 
                 // 'Unary sub' signed int or lit => int | signed
                 // 'Cmp Not'   bool => bool
@@ -792,24 +1041,43 @@ impl Checker {
                     ExprForm::Expr { inherited_width } => inherited_width,
                 };
                 match op {
-                    TokenKind::Tilde => match checked.addr_mode  {
+                    TokenKind::Tilde => match checked.addr_mode {
+                        _ if matches!(
+                            checked.type_mode,
+                            TypeMode::Float { .. } | TypeMode::FloatLit
+                        ) =>
+                        {
+                            err!(
+                                operand.span(),
+                                "'~' unary operator requires expr to be an integer, found {}",
+                                self.fmt_exprdata(&checked)
+                            )
+                        }
                         AddressingMode::Primitive => Ok(checked),
-                        _ => err!(self, "'~' unary operator requires 'primitive' addressing =>\n{checked:#?}")
-                    }
+                        _ => err!(
+                            operand.span(),
+                            "'~' unary operator requires 'primitive' addressing, found {}",
+                            self.fmt_exprdata(&checked)
+                        ),
+                    },
                     TokenKind::Sub => match checked.type_mode {
-                        TypeMode::Int { signed } | TypeMode::Float { signed } if signed => {
-                            Ok(ExprData {
-                                type_mode: TypeMode::Int { signed },
-                                addr_mode: AddressingMode::Primitive,
-                                form: ExprForm::Expr { inherited_width },
-                            })
-                        }
+                        TypeMode::Int { signed: true }
+                        | TypeMode::Float { .. }
+                        | TypeMode::FloatLit => Ok(ExprData {
+                            type_mode: checked.type_mode,
+                            addr_mode: AddressingMode::Primitive,
+                            form: ExprForm::Expr { inherited_width },
+                        }),
                         TypeMode::IntLit => Ok(ExprData {
                             type_mode: TypeMode::Int { signed: true },
                             addr_mode: AddressingMode::Primitive,
                             form: ExprForm::Expr { inherited_width },
                         }),
-                        _ => err!(self, "'-' unary operator requires expr to be a signed integers =>\n{checked:#?}"),
+                        _ => err!(
+                            operand.span(),
+                            "'-' unary operator requires expr to be a signed integer, found {}",
+                            self.fmt_exprdata(&checked)
+                        ),
                     },
                     TokenKind::CmpNot => match checked.type_mode {
                         TypeMode::Bool => Ok(ExprData {
@@ -817,51 +1085,149 @@ impl Checker {
                             addr_mode: AddressingMode::Primitive,
                             form: ExprForm::Expr { inherited_width },
                         }),
-                        _ => err!(self, "'!' unary operator requires expr to be a boolean =>\n{checked:#?}"),
+                        _ => err!(
+                            operand.span(),
+                            "'!' unary operator requires expr to be a boolean, found {}",
+                            self.fmt_exprdata(&checked)
+                        ),
                     },
                     TokenKind::Ampersand => match checked.addr_mode {
                         AddressingMode::Primitive => match checked.form {
-                            ExprForm::Variable { .. } => Ok(ExprData { // TODO(TOM): use variable's ptr?
+                            ExprForm::Variable { .. } => Ok(ExprData {
+                                // TODO(TOM): use variable's ptr?
                                 type_mode: checked.type_mode,
                                 addr_mode: AddressingMode::Pointer,
                                 form: ExprForm::Expr {
                                     inherited_width: PTR_WIDTH,
                                 },
                             }),
-                            _ => err!(self, "'&' unary operator requires expr to be a memory address."),
+                            _ => err!(
+                                operand.span(),
+                                "'&' unary operator requires expr to be a memory address."
+                            ),
                         },
-                        _ => err!(self, "'&' unary operator requires expr to have a memory address =>\n{checked:#?}"),
+                        _ => err!(
+                            operand.span(),
+                            "'&' unary operator requires expr to have a memory address, found {}",
+                            self.fmt_exprdata(&checked)
+                        ),
                     },
                     TokenKind::Ptr => match checked.addr_mode {
                         AddressingMode::Pointer => Ok(ExprData {
                             type_mode: checked.type_mode,
                             addr_mode: AddressingMode::Primitive,
-                            form: ExprForm::Expr { inherited_width }, // TODO(TOM): not sure about this?
+                            // as wide as what it points to, not the pointer.
+                            form: ExprForm::Expr {
+                                inherited_width: match checked.form {
+                                    ExprForm::Variable { ptr } => {
+                                        let type_id = unsafe { (*ptr.as_ptr()).type_id };
+                                        self.types.get(type_id).unwrap().width
+                                    }
+                                    ExprForm::Expr { .. } => inherited_width,
+                                },
+                            },
                         }),
-                        _ => err!(self, "'^' unary operator requires expr to be a pointer =>\n{checked:#?}"),
+                        _ => err!(
+                            operand.span(),
+                            "'^' unary operator requires expr to be a pointer, found {}",
+                            self.fmt_exprdata(&checked)
+                        ),
                     },
-                    _ => err!(self, "Illegal unary Expression '{op:?}' =>\n{checked:#?}"),
+                    _ => err!(
+                        *span,
+                        "Illegal unary Expression '{op:?}' on {}",
+                        self.fmt_exprdata(&checked)
+                    ),
                 }
             }
-            NodeExpr::Term(term) => self.check_term(term),
-        }
-    }
+            NodeExpr::Cast {
+                expr,
+                type_tok,
+                span,
+            } => {
+                let checked = self.check_expr(expr)?;
+                let cast_type = self.types.get(self.get_type_id(type_tok)?).unwrap();
+                let cast_mode = match cast_type.form {
+                    TypeForm::Base { type_mode } => type_mode,
+                    TypeForm::Struct { .. } | TypeForm::Union {} => {
+                        return err!(
+                            *span,
+                            "Invalid cast from {} to '{}'",
+                            self.fmt_exprdata(&checked),
+                            type_tok.as_str()
+                        )
+                    }
+                };
 
-    fn check_term(&self, term: &NodeTerm) -> Result<ExprData, String> {
-        match term {
-            NodeTerm::IntLit(tok) => {
-                self.update_pos(tok.pos);
+                // numbers cast to any number, 'char' only to and from 'u8'.
+                let from_width = self.get_width(&checked.form);
+                let valid = checked.addr_mode == AddressingMode::Primitive
+                    && match (checked.type_mode, cast_mode) {
+                        (from, to) if from == to => true,
+                        (
+                            TypeMode::Int { .. }
+                            | TypeMode::IntLit
+                            | TypeMode::Float { .. }
+                            | TypeMode::FloatLit,
+                            TypeMode::Int { .. } | TypeMode::Float { .. },
+                        ) => true,
+                        (TypeMode::Char, TypeMode::Int { signed: false }) => cast_type.width == 1,
+                        (TypeMode::Int { signed: false }, TypeMode::Char) => from_width == 1,
+                        (TypeMode::IntLit, TypeMode::Char) => true,
+                        _ => false,
+                    };
+                if !valid {
+                    let e = Diagnostic::error(
+                        CODE,
+                        format!(
+                            "Invalid cast from {} to '{}'",
+                            self.fmt_exprdata(&checked),
+                            type_tok.as_str()
+                        ),
+                    )
+                    .with_span(*span);
+                    return match checked.type_mode == TypeMode::Char || cast_mode == TypeMode::Char
+                    {
+                        true => Err(e.with_help("'char' only casts to and from 'u8'")),
+                        false => Err(e),
+                    };
+                }
 
                 Ok(ExprData {
-                    type_mode: TypeMode::IntLit,
+                    type_mode: cast_mode,
                     addr_mode: AddressingMode::Primitive,
-                    form: ExprForm::Expr { inherited_width: 0 },
+                    form: ExprForm::Expr {
+                        inherited_width: cast_type.width,
+                    },
                 })
             }
-            NodeTerm::Ident(tok) => {
-                self.update_pos(tok.pos);
+            NodeExpr::Term(term) => self.check_term(term),
+        }
+    }
+
+    fn check_condition(&self, condition: &mut NodeExpr, stmt_name: &str) -> Result<(), Diagnostic> {
+        let checked = self.check_expr(condition)?;
+        match checked.type_mode {
+            TypeMode::Bool => Ok(()),
+            _ => err!(
+                condition.span(),
+                "'{stmt_name}' statement condition not 'boolean', found {}",
+                self.fmt_exprdata(&checked)
+            ),
+        }
+    }
 
-                let var = self.get_var(tok.as_str())?;
+    fn check_term(&self, term: &mut NodeTerm) -> Result<ExprData, Diagnostic> {
+        match term {
+            NodeTerm::IntLit(tok) => self.check_int_lit(tok, false),
+            NodeTerm::FloatLit(tok) => self.check_float_lit(tok),
+            NodeTerm::CharLit(_) => Ok(ExprData {
+                type_mode: TypeMode::Char,
+                addr_mode: AddressingMode::Primitive,
+                form: ExprForm::Expr { inherited_width: 1 },
+            }),
+            NodeTerm::Ident(tok) => {
+                let var = self.get_var(tok)?;
                 match &self.types.get(var.type_id).unwrap().form {
                     TypeForm::Base { type_mode } => Ok(ExprData {
                         type_mode: *type_mode,
@@ -877,7 +1243,7 @@ impl Checker {
                 }
             }
 
-            NodeTerm::True | NodeTerm::False => {
+            NodeTerm::True(_) | NodeTerm::False(_) => {
                 let type_ref = self.types.get(*self.type_map.get("bool").unwrap()).unwrap();
                 match &type_ref.form {
                     TypeForm::Base { type_mode } => Ok(ExprData {
@@ -891,120 +1257,232 @@ impl Checker {
                     TypeForm::Union {} => todo!("check_term boolean union"),
                 }
             }
-            NodeTerm::FnCall { ident, args } => {
-                self.update_pos(ident.pos);
-
-                // check fn of that name exists
-                // iterating over hash map aswell! bad!!!
-
-                // check args are of valid type
-                // for (i, arg) in args.into_iter().enumerate() {
-                //     let arg_expr = self.check_expr(&arg)?;
-                //     let fn_arg =
-                //         self.get_exprdata(fn_ref.arg_semantics.get(i).as_ref().unwrap())?;
-                //     self.check_type_equivalence(&fn_arg, &arg_expr)?;
-                // }
-
-                let fn_str = ident.as_str();
+            NodeTerm::FnCall {
+                ident,
+                args,
+                span,
+                signature,
+            } => {
                 let mut args_data = Vec::with_capacity(args.len());
-                for arg in args.into_iter() {
+                for arg in args.iter_mut() {
                     args_data.push(self.check_expr(arg)?);
                 }
 
-                // construct a function signature!!
-                let signature = match ident.as_str() {
-                    "main" => "main".to_owned(),
-                    name @ _ => {
-                        let mut str = String::new();
-                        str += name;
-                        str += "(";
-                        for (i, arg) in args_data.iter().enumerate() {
-                            // ExprData => Type
-                            // str += self.types.get(arg.type_id).unwrap().ident.as_str();
-                            str += ",";
+                // overloads share a name, sorted so the same one is always tried first.
+                let name = ident.as_str();
+                let mut overloads: Vec<&SemFn> = self
+                    .fn_map
+                    .values()
+                    .filter(|func| func.signature.split('(').next() == Some(name))
+                    .collect();
+                overloads.sort_by(|a, b| a.signature.cmp(&b.signature));
+                if overloads.is_empty() {
+                    return err!(
+                        ident.span,
+                        "No associated function with attempted call. '{name}'"
+                    );
+                }
+
+                // the first overload whose parameters take every argument.
+                let mut first_err = None;
+                for func in overloads
+                    .iter()
+                    .filter(|func| func.arg_semantics.len() == args.len())
+                {
+                    let mut params = args.iter().zip(&args_data).zip(&func.arg_semantics);
+                    let result = params.try_for_each(|((arg, data), param)| {
+                        let param_data = self.get_exprdata(param)?;
+                        self.check_type_equivalence(&param_data, data, arg.span())
+                    });
+                    match result {
+                        Ok(()) => {
+                            let return_data = func.return_type_data.unwrap_or(ExprData {
+                                type_mode: TypeMode::Void,
+                                addr_mode: AddressingMode::Primitive,
+                                form: ExprForm::Expr { inherited_width: 0 },
+                            });
+                            *signature = Some(func.signature.clone());
+                            return Ok(return_data);
+                        }
+                        Err(e) => {
+                            let note = format!("calling '{}'", func.signature);
+                            first_err.get_or_insert(e.with_note(note));
                         }
-                        str.pop(); // removes extra ','
-                        str + ")"
                     }
-                };
-
-                // iterate over fn_map
-                // compare to attempted fncall
-                //      - amount of args first
-                //      - compare each arg id.
-                //      - then by name (delimit by '(')
-                // match to see if associated function is found for call.
-                // for (sig, fn_ref) in &self.fn_map {
-                //     if fn_ref.arg_semantics.len() != args.len() {
-                //         continue;
-                //     }
-                // }
-
-                // let is_fn_name_valid = self.fn_map.iter().find(|(sig, fn_ref)| {
-                //     sig.as_str()
-                //         .split('(')
-                //         .collect::<Vec<&str>>()
-                //         .get(0)
-                //         .unwrap()
-                //         == &fn_str
-                // });
-                // let (signature, fn_ref) = match is_fn_name_valid {
-                //     Some((sig, fn_ref)) => (sig.as_str(), fn_ref),
-                //     None => {
-                //         return err!(
-                //             self,
-                //             "No associated function with attempted call. '{fn_str}'"
-                //         )
-                //     }
-                // };
-
-                // check correct amount of arguments
-                // if args.len() != fn_ref.arg_semantics.len() {
-                //     return err!(
-                //         self,
-                //         "Incorrect amount of arguments for function '{signature}'. {} missing",
-                //         fn_ref.arg_semantics.len() - args.len()
-                //     );
-                // }
-
-                Ok(fn_ref.return_type_data.unwrap())
+                }
+                match first_err {
+                    Some(e) => Err(e),
+                    None => {
+                        let counts: Vec<String> = overloads
+                            .iter()
+                            .map(|func| func.arg_semantics.len().to_string())
+                            .collect();
+                        err!(
+                            *span,
+                            "Wrong number of arguments for '{name}', expected {}, found {}",
+                            counts.join(" or "),
+                            args.len()
+                        )
+                    }
+                }
             }
         }
     }
 
-    // AddrMode, TypeMode, Width
+    // AddrMode, TypeMode, Width. 'span' is the assignee's expression.
     fn check_type_equivalence(
         &self,
         assigner: &ExprData,
         assignee: &ExprData,
-    ) -> Result<(), String> {
+        span: Span,
+    ) -> Result<(), Diagnostic> {
         // Check Addressing Mode
         if assigner.addr_mode != assignee.addr_mode {
             return err!(
-                self,
-                "Expr of different AddrMode! {:?} vs {:?} =>\n{assigner:#?}\n.. {assignee:#?}",
-                assigner.addr_mode,
-                assignee.addr_mode
+                span,
+                "Expr of different AddrMode! {} vs {}",
+                self.fmt_exprdata(assigner),
+                self.fmt_exprdata(assignee)
             );
         }
 
         // Check Type Mode
-        let msg = format!("Expr of different Type! =>\n{assigner:#?}\n.. {assignee:#?}");
-        self.check_type_mode(assigner.type_mode, assignee.type_mode, &msg)?;
+        let msg = format!(
+            "Expr of different Type! {} .. {}",
+            self.fmt_exprdata(assigner),
+            self.fmt_exprdata(assignee)
+        );
+        self.check_type_mode(assigner.type_mode, assignee.type_mode, &msg, span)?;
 
         // Check for Type Narrowing
         let assigner_width = self.get_width(&assigner.form);
         let assignee_width = self.get_width(&assignee.form);
         if assigner_width < assignee_width {
             return err!(
-                self,
-                "Illegal Type Narrowing, Assignee({assignee_width}) < Assigner({assigner_width}) =>\n{assigner:#?}\n.. {assignee:#?}"
+                span,
+                "Illegal Type Narrowing, {} is wider than {}",
+                self.fmt_exprdata(assignee),
+                self.fmt_exprdata(assigner)
             );
         }
         Ok(())
     }
 
-    fn get_exprdata(&self, var: &SemVariable) -> Result<ExprData, String> {
+    // a loop's label can't be reused by a loop inside it, 'break 'outer' would be ambiguous.
+    fn push_loop(&mut self, label: &Option<Token>) -> Result<(), Diagnostic> {
+        if let Some(label) = label {
+            let name = label.as_str();
+            let mut outer = self.ctx.loops.iter().flatten();
+            if let Some(first) = outer.find(|tok| tok.as_str() == name) {
+                return Err(Diagnostic::error(
+                    CODE,
+                    format!("Label {name} is already used by an outer loop"),
+                )
+                .with_span(label.span)
+                .with_label(first.span, "first used here"));
+            }
+        }
+        self.ctx.loops.push(label.clone());
+        Ok(())
+    }
+
+    // 'break' and 'continue' need a loop around them, a label must name one of those loops.
+    fn check_loop_jump(
+        &self,
+        label: &Option<Token>,
+        span: Span,
+        name: &str,
+    ) -> Result<(), Diagnostic> {
+        if self.ctx.loops.is_empty() {
+            return err!(span, "Not inside a loop! cannot {name}");
+        }
+        let Some(label) = label else {
+            return Ok(());
+        };
+        let label_str = label.as_str();
+        if !self
+            .ctx
+            .loops
+            .iter()
+            .flatten()
+            .any(|tok| tok.as_str() == label_str)
+        {
+            return Err(
+                Diagnostic::error(CODE, format!("Undeclared loop label: {label_str}"))
+                    .with_span(label.span)
+                    .with_help("a label names a loop around it, e.g \"'outer: while ..\""),
+            );
+        }
+        Ok(())
+    }
+
+    // An assignment's target, a variable must be mutable unless this is its deferred initialisation.
+    // .. writing through a pointer, 'p^ = 5', doesn't change 'p' itself.
+    fn check_target(
+        &mut self,
+        target: &mut NodeExpr,
+        compound: bool,
+    ) -> Result<ExprData, Diagnostic> {
+        let ident = match target {
+            NodeExpr::Term(NodeTerm::Ident(ident)) => ident,
+            _ => return self.check_expr(target),
+        };
+        let var = self.get_var(ident)?;
+        let var_data = self.get_exprdata(var)?;
+        if !var.mutable {
+            match var.init_expr {
+                InitExpr::None if !compound => {
+                    let var_mut = self.get_var_mut(ident)?;
+                    var_mut.init_expr = InitExpr::Deferred
+                }
+                _ => {
+                    let name = ident.as_str();
+                    return Err(Diagnostic::error(
+                        CODE,
+                        format!("Re-assignment of a Constant: '{name}'"),
+                    )
+                    .with_span(ident.span)
+                    .with_label(var.ident.span, "defined here")
+                    .with_help(format!("make it mutable: 'let mut {name}'")));
+                }
+            }
+        }
+        Ok(var_data)
+    }
+
+    // A new variable can't reuse a name in scope, or a type's name.
+    fn check_var_name(&self, ident: &Token) -> Result<(), Diagnostic> {
+        let str = ident.as_str();
+        if let Some(idx) = self.var_map.get(str) {
+            let first_span = self.vars.get(*idx).unwrap().ident.span;
+            return Err(Diagnostic::error(
+                CODE,
+                format!("Duplicate definition of a Variable: '{str}'"),
+            )
+            .with_span(ident.span)
+            .with_label(first_span, "first defined here"));
+        } else if self.type_map.contains_key(str) {
+            return err!(
+                ident.span,
+                "Illegal Variable name, Types are reserved: '{str}'"
+            );
+        }
+        Ok(())
+    }
+
+    // The type of an expression, 'None' for literals which fit any.
+    fn infer_type_id(&self, data: &ExprData) -> Option<usize> {
+        match (data.form, data.type_mode) {
+            (ExprForm::Variable { ptr }, _) => Some(unsafe { (*ptr.as_ptr()).type_id }),
+            (ExprForm::Expr { inherited_width }, TypeMode::Int { signed }) => {
+                let ident = format!("{}{}", if signed { "i" } else { "u" }, inherited_width * 8);
+                self.type_map.get(&ident).copied()
+            }
+            _ => None,
+        }
+    }
+
+    fn get_exprdata(&self, var: &SemVariable) -> Result<ExprData, Diagnostic> {
         match &self.types.get(var.type_id).unwrap().form {
             TypeForm::Base { type_mode } => Ok(ExprData {
                 type_mode: *type_mode,
@@ -1025,11 +1503,33 @@ impl Checker {
         assigner: TypeMode,
         assignee: TypeMode,
         msg: &str,
-    ) -> Result<(), String> {
+        span: Span,
+    ) -> Result<(), Diagnostic> {
         if assigner == assignee {
             return Ok(());
         }
 
+        // a 'char' is only a number after an explicit cast.
+        if assigner == TypeMode::Char || assignee == TypeMode::Char {
+            return Err(Diagnostic::error(CODE, msg.to_string())
+                .with_span(span)
+                .with_help("convert with 'as', 'char' casts to and from 'u8'"));
+        }
+
+        // a float only meets floats, an integer must be cast first.
+        let is_float = |mode| matches!(mode, TypeMode::Float { .. } | TypeMode::FloatLit);
+        let is_int = |mode| matches!(mode, TypeMode::Int { .. } | TypeMode::IntLit);
+        if is_float(assigner) || is_float(assignee) {
+            let e = Diagnostic::error(CODE, msg.to_string()).with_span(span);
+            return match (assigner, assignee) {
+                (a, b) if is_float(a) && is_float(b) => Ok(()),
+                (a, b) if is_int(a) || is_int(b) => Err(e.with_help(
+                    "convert with 'as', e.g 'x as f64', float literals need a '.', e.g '2.0'",
+                )),
+                _ => Err(e),
+            };
+        }
+
         // Check integer sign equality
         let sign_match = match assigner {
             TypeMode::IntLit => return Ok(()),
@@ -1038,14 +1538,14 @@ impl Checker {
                 TypeMode::Int { signed: sign2 } | TypeMode::Float { signed: sign2 } => {
                     sign1 == sign2
                 }
-                TypeMode::Bool | TypeMode::Void => false,
+                TypeMode::Bool | TypeMode::Void | TypeMode::Char | TypeMode::FloatLit => false,
             },
-            TypeMode::Bool | TypeMode::Void => false,
+            TypeMode::Bool | TypeMode::Void | TypeMode::Char | TypeMode::FloatLit => false,
         };
 
         if !sign_match {
             return err!(
-                self,
+                span,
                 "Expr sign mismatch! {assigner:?} vs {assignee:?} => {msg}"
             );
         }
@@ -1058,23 +1558,27 @@ impl Checker {
         match expr {
             NodeExpr::BinaryExpr { lhs, rhs, .. } => {
                 if right_side {
-                    self.get_expr_ident(&*rhs, false)
+                    self.get_expr_ident(rhs, false)
                 } else {
-                    self.get_expr_ident(&*lhs, false)
+                    self.get_expr_ident(lhs, false)
                 }
             }
-            NodeExpr::UnaryExpr { operand, .. } => self.get_expr_ident(&*operand, false),
+            NodeExpr::UnaryExpr { operand, .. } => self.get_expr_ident(operand, false),
+            NodeExpr::Cast { expr, .. } => self.get_expr_ident(expr, right_side),
             NodeExpr::Term(term) => match term {
-                NodeTerm::True => "true".to_string(),
-                NodeTerm::False => "false".to_string(),
+                NodeTerm::True(_) => "true".to_string(),
+                NodeTerm::False(_) => "false".to_string(),
                 NodeTerm::IntLit(tok)
+                | NodeTerm::FloatLit(tok)
+                | NodeTerm::CharLit(tok)
                 | NodeTerm::Ident(tok)
                 | NodeTerm::FnCall { ident: tok, .. } => tok.as_str().to_string(),
             },
         }
     }
 
-    fn get_var(&self, ident: &str) -> Result<&SemVariable, String> {
+    fn get_var(&self, tok: &Token) -> Result<&SemVariable, Diagnostic> {
+        let ident = tok.as_str();
         match self.var_map.get(ident) {
             Some(idx) if self.ctx.scope_inherit_bounds_id.is_none() => {
                 Ok(self.vars.get(*idx).unwrap())
@@ -1083,7 +1587,7 @@ impl Checker {
                 let var = self.vars.get(*idx).unwrap();
                 if var.scope_id < self.ctx.scope_inherit_bounds_id.unwrap() {
                     return err!(
-                        self,
+                        tok.span,
                         "Variable '{ident}' outside scope inheritance bounds, {} < {}",
                         var.scope_id,
                         self.ctx.scope_inherit_bounds_id.unwrap()
@@ -1091,11 +1595,12 @@ impl Checker {
                 }
                 Ok(var)
             }
-            None => err!(self, "Variable '{ident}' not found"),
+            None => err!(tok.span, "Variable '{ident}' not found"),
         }
     }
 
-    fn get_var_mut(&mut self, ident: &str) -> Result<&mut SemVariable, String> {
+    fn get_var_mut(&mut self, tok: &Token) -> Result<&mut SemVariable, Diagnostic> {
+        let ident = tok.as_str();
         match self.var_map.get(ident) {
             Some(idx) if self.ctx.scope_inherit_bounds_id.is_none() => {
                 Ok(self.vars.get_mut(*idx).unwrap())
@@ -1104,7 +1609,7 @@ impl Checker {
                 let var = self.vars.get_mut(*idx).unwrap();
                 if var.scope_id < self.ctx.scope_inherit_bounds_id.unwrap() {
                     return err!(
-                        self,
+                        tok.span,
                         "Variable '{ident}' outside scope inheritance bounds, {} < {}",
                         var.scope_id,
                         self.ctx.scope_inherit_bounds_id.unwrap()
@@ -1112,14 +1617,124 @@ impl Checker {
                 }
                 Ok(var)
             }
-            None => err!(self, "Variable '{ident}' not found"),
+            None => err!(tok.span, "Variable '{ident}' not found"),
         }
     }
 
-    fn get_type_id(&self, ident: &str) -> Result<usize, String> {
+    // An unsuffixed literal coerces to whatever it meets, a suffix fixes its type, e.g '255u8'.
+    // .. 'negated' literals may reach one further, '-128i8' fits but '128i8' does not.
+    fn check_int_lit(&self, tok: &Token, negated: bool) -> Result<ExprData, Diagnostic> {
+        let (value, suffix) = tok.int_lit();
+        let Some(suffix) = suffix else {
+            return Ok(ExprData {
+                type_mode: TypeMode::IntLit,
+                addr_mode: AddressingMode::Primitive,
+                form: ExprForm::Expr { inherited_width: 0 },
+            });
+        };
+        let int_type = match INT_SUFFIXES.contains(&suffix) {
+            true => self.types.get(self.type_map[suffix]).unwrap(),
+            false => {
+                return Err(Diagnostic::error(
+                    CODE,
+                    format!("Invalid suffix '{suffix}' for integer literal"),
+                )
+                .with_span(tok.span)
+                .with_help(format!("valid suffixes are: {}", INT_SUFFIXES.join(", "))));
+            }
+        };
+        let TypeForm::Base {
+            type_mode: TypeMode::Int { signed },
+        } = int_type.form
+        else {
+            unreachable!()
+        };
+
+        let bits = int_type.width as u32 * 8;
+        let (min, max) = match signed {
+            true => (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1),
+            false => (0, (1i128 << bits) - 1),
+        };
+        let value = match negated {
+            true => -(value as i128),
+            false => value as i128,
+        };
+        if !(min..=max).contains(&value) {
+            return err!(
+                tok.span,
+                "Literal out of range for '{suffix}', which holds {min}..={max}"
+            );
+        }
+        Ok(ExprData {
+            type_mode: TypeMode::Int { signed },
+            addr_mode: AddressingMode::Primitive,
+            form: ExprForm::Expr {
+                inherited_width: int_type.width,
+            },
+        })
+    }
+
+    // Like 'check_int_lit', '1.5' coerces to either float and '1.5f32' is fixed to 'f32'.
+    fn check_float_lit(&self, tok: &Token) -> Result<ExprData, Diagnostic> {
+        let (value, suffix) = tok.float_lit();
+        let (type_mode, width) = match suffix {
+            None => (TypeMode::FloatLit, 0),
+            Some(suffix @ ("f32" | "f64")) => {
+                let float_type = self.types.get(self.type_map[suffix]).unwrap();
+                if float_type.width == 4 && (value as f32).is_infinite() {
+                    return err!(tok.span, "Literal out of range for 'f32'");
+                }
+                (TypeMode::Float { signed: true }, float_type.width)
+            }
+            Some(suffix) => {
+                return Err(Diagnostic::error(
+                    CODE,
+                    format!("Invalid suffix '{suffix}' for float literal"),
+                )
+                .with_span(tok.span)
+                .with_help("valid suffixes are: f32, f64"));
+            }
+        };
+        Ok(ExprData {
+            type_mode,
+            addr_mode: AddressingMode::Primitive,
+            form: ExprForm::Expr {
+                inherited_width: width,
+            },
+        })
+    }
+
+    fn get_type_id(&self, tok: &Token) -> Result<usize, Diagnostic> {
+        let ident = tok.as_str();
         match self.type_map.get(ident) {
             Some(id) => Ok(*id),
-            None => err!(self, "Type '{ident}' not found"),
+            None => err!(tok.span, "Type '{ident}' not found"),
+        }
+    }
+
+    // A short description for error messages, e.g. 'i32', '^u8' or 'integer literal'.
+    fn fmt_exprdata(&self, data: &ExprData) -> String {
+        let type_str = match (data.form, data.type_mode) {
+            (ExprForm::Variable { ptr }, _) => {
+                let type_id = unsafe { (*ptr.as_ptr()).type_id };
+                self.types.get(type_id).unwrap().ident.clone()
+            }
+            (_, TypeMode::IntLit) => return "'integer literal'".to_string(),
+            (_, TypeMode::FloatLit) => return "'float literal'".to_string(),
+            (_, TypeMode::Void) => "void".to_string(),
+            (_, TypeMode::Bool) => "bool".to_string(),
+            (_, TypeMode::Char) => "char".to_string(),
+            (ExprForm::Expr { inherited_width }, TypeMode::Int { signed }) => {
+                format!("{}{}", if signed { "i" } else { "u" }, inherited_width * 8)
+            }
+            (ExprForm::Expr { inherited_width }, TypeMode::Float { .. }) => {
+                format!("f{}", inherited_width * 8)
+            }
+        };
+        match data.addr_mode {
+            AddressingMode::Primitive => format!("'{type_str}'"),
+            AddressingMode::Pointer => format!("'^{type_str}'"),
+            AddressingMode::Array => format!("'[]{type_str}'"),
         }
     }
 
@@ -1136,19 +1751,13 @@ impl Checker {
         self.types.push(new_type);
     }
 
-    fn update_pos(&self, pos: (u32, u32)) {
-        unsafe {
-            let mut_self = self as *const Checker as *mut Checker;
-            (*mut_self).pos = pos;
-        }
-    }
-
-    fn new_nonnull(&self, reference: &SemVariable) -> Result<NonNull<SemVariable>, String> {
+    fn new_nonnull(&self, reference: &SemVariable) -> Result<NonNull<SemVariable>, Diagnostic> {
         match NonNull::new(reference as *const SemVariable as *mut SemVariable) {
             Some(ptr) => Ok(ptr),
             None => err!(
-                self,
-                "Found nullptr when creating 'ExprData'\n{reference:#?}"
+                reference.ident.span,
+                "Found nullptr when creating 'ExprData' for '{}'",
+                reference.ident.as_str()
             ),
         }
     }
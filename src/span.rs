@@ -0,0 +1,113 @@
+// >>SPAN<< Where something is in the source, as byte offsets.
+//  - every token and AST node has one, errors point at exactly what's wrong.
+//  - the source map turns them into a file name, line and column when printing.
+use std::fmt;
+
+// 'lo'..'hi' bytes of file 'file_id', end exclusive.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Span {
+    pub file_id: u32,
+    pub lo: u32,
+    pub hi: u32,
+}
+
+impl Span {
+    pub fn new(file_id: u32, lo: u32, hi: u32) -> Span {
+        Span { file_id, lo, hi }
+    }
+
+    // From the start of 'self' to the end of 'other'.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            file_id: self.file_id,
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+
+    // The empty span just after 'self', e.g. where a missing ';' should go.
+    pub fn shrink_to_hi(self) -> Span {
+        Span {
+            lo: self.hi,
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> u32 {
+        self.hi - self.lo
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lo == self.hi
+    }
+}
+
+// 'Span(file 0, 12..15)' is a lot of noise in the emitted ast and asm comments.
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.lo, self.hi)
+    }
+}
+
+pub struct SourceFile {
+    pub name: String,
+    pub src: String,
+    line_starts: Vec<u32>, // byte offset of each line
+}
+
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> SourceMap {
+        SourceMap { files: Vec::new() }
+    }
+
+    // Returns the 'file_id' for the spans of this file.
+    pub fn add_file(&mut self, name: String, src: String) -> u32 {
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            src.bytes()
+                .enumerate()
+                .filter(|(_, byte)| *byte == b'\n')
+                .map(|(n, _)| n as u32 + 1),
+        );
+        self.files.push(SourceFile {
+            name,
+            src,
+            line_starts,
+        });
+        self.files.len() as u32 - 1
+    }
+
+    pub fn file(&self, file_id: u32) -> &SourceFile {
+        &self.files[file_id as usize]
+    }
+
+    // 1-based line and column of a byte offset, columns count characters not bytes.
+    pub fn line_col(&self, file_id: u32, offset: u32) -> (u32, u32) {
+        let file = self.file(file_id);
+        let line = match file.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = file.line_starts[line] as usize;
+        let end = (offset as usize).min(file.src.len());
+        let col = String::from_utf8_lossy(&file.src.as_bytes()[line_start..end])
+            .chars()
+            .count();
+        (line as u32 + 1, col as u32 + 1)
+    }
+
+    // The text of a 1-based line, without its newline.
+    pub fn line_text(&self, file_id: u32, line: u32) -> &str {
+        let file = self.file(file_id);
+        let start = match file.line_starts.get(line as usize - 1) {
+            Some(start) => *start as usize,
+            None => return "",
+        };
+        file.src[start..].lines().next().unwrap_or("")
+    }
+}
@@ -0,0 +1,499 @@
+// The library API, compiling in-process without spawning the binary.
+use std::collections::VecDeque;
+
+use rust_compiler::{
+    compile, compile_with_warnings, lex,
+    parse::{NodeExpr, NodeStmt, NodeTerm},
+    Emit, Lexer, Options, Output, Parser, Severity, SourceMap, Token, TokenKind, TriviaKind,
+};
+
+// Tokens of source that should lex cleanly.
+fn lex_ok(mut lexer: Lexer) -> VecDeque<Token> {
+    let (tokens, errors) = lexer.tokenize();
+    assert!(errors.is_empty(), "{errors:?}");
+    tokens
+}
+
+#[test]
+fn compiles_to_asm() {
+    let source = "fn main() {\n    exit(2);\n}\n";
+    match compile(source, &Options::default()) {
+        Ok(Output::Asm(asm)) => assert!(asm.contains("main:"), "{asm}"),
+        Ok(_) => panic!("expected asm"),
+        Err(errors) => panic!("{errors:?}"),
+    }
+}
+
+#[test]
+fn stops_after_emitted_stage() {
+    let options = Options {
+        emit: Emit::Tokens,
+        ..Options::default()
+    };
+    assert!(matches!(
+        compile("fn main() {}", &options),
+        Ok(Output::Tokens(_))
+    ));
+}
+
+#[test]
+fn returns_every_diagnostic() {
+    let source = "fn main() {\n    let x: i32 = 5\n    let y: i32 = ;\n}\n";
+    let errors = match compile(source, &Options::default()) {
+        Ok(_) => panic!("expected errors"),
+        Err(errors) => errors,
+    };
+    assert_eq!(errors.len(), 2, "{errors:?}");
+
+    let mut sources = SourceMap::new();
+    sources.add_file("main.tc".to_string(), source.to_string());
+    let rendered = errors[1].render(&sources, false);
+    assert!(rendered.contains("--> main.tc:3:18"), "{rendered}");
+}
+
+#[test]
+fn lexes_string_escapes() {
+    let tokens = lex_ok(Lexer::new(0, r#"let s = "a\tb\n\x41\0\\\"" + "c//d";"#));
+    let strings: Vec<&str> = tokens
+        .iter()
+        .filter(|tok| tok.kind == TokenKind::StrLit)
+        .map(|tok| tok.as_str())
+        .collect();
+    assert_eq!(strings, ["a\tb\nA\0\\\"", "c//d"]);
+}
+
+#[test]
+fn reports_unterminated_string() {
+    let errors = Lexer::new(0, "let s = \"open\nexit(1);").tokenize().1;
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].message, "Unterminated string literal");
+    assert_eq!(errors[0].span.map(|span| (span.lo, span.hi)), Some((8, 13)));
+}
+
+#[test]
+fn lexes_float_literals() {
+    // the '0' before a range's '..' mustn't become a float.
+    let tokens = lex_ok(Lexer::new(0, "1.5 2e10 3.0f32 4f64 1_0.2_5e-1 0..5"));
+    let kinds: Vec<TokenKind> = tokens.iter().map(|tok| tok.kind).collect();
+    assert_eq!(kinds[..5], [TokenKind::FloatLit; 5]);
+    let floats: Vec<(f64, Option<&str>)> =
+        tokens.iter().take(5).map(|tok| tok.float_lit()).collect();
+    assert_eq!(
+        floats,
+        [
+            (1.5, None),
+            (2e10, None),
+            (3.0, Some("f32")),
+            (4.0, Some("f64")),
+            (1.025, None)
+        ]
+    );
+    assert_eq!(
+        kinds[5..],
+        [TokenKind::IntLit, TokenKind::DotDot, TokenKind::IntLit]
+    );
+}
+
+#[test]
+fn lexes_unicode_source() {
+    let source = "/* コメント */ let größe = \"héllo ☃\"; // ☃\n";
+    let errors = Lexer::new(0, source).tokenize().1;
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].message, "Non-ascii identifier 'größe'");
+
+    let tokens = lex_ok(Lexer::new(0, source).unicode_idents(true));
+    let values: Vec<&str> = tokens
+        .iter()
+        .filter(|tok| tok.value.is_some())
+        .map(|tok| tok.as_str())
+        .collect();
+    assert_eq!(values, ["größe", "héllo ☃"]);
+}
+
+#[test]
+fn columns_count_chars() {
+    let source = "fn main() { /* ☃☃ */ exit(☃); }\n";
+    let errors = Lexer::new(0, source).tokenize().1;
+    let mut sources = SourceMap::new();
+    sources.add_file("main.tc".to_string(), source.to_string());
+    let rendered = errors[0].render(&sources, false);
+    assert!(rendered.contains("--> main.tc:1:27"), "{rendered}");
+}
+
+#[test]
+fn nests_block_comments() {
+    let errors = Lexer::new(0, "/* a /* b */ c */ exit(1); /* /* */")
+        .tokenize()
+        .1;
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].message, "Unterminated block comment");
+    assert_eq!(errors[0].span.map(|span| span.lo), Some(27));
+
+    let tokens = lex_ok(Lexer::new(0, "/* a /* b */ c */ exit(1);"));
+    assert_eq!(tokens[0].kind, TokenKind::Exit);
+}
+
+#[test]
+fn attaches_doc_comments() {
+    let source = "/// Adds one.\n/// Twice.\nfn inc() {}\n//// not a doc\nfn main() {}\n";
+    let tokens = lex_ok(Lexer::new(0, source));
+    let docs: Vec<&str> = tokens
+        .iter()
+        .filter(|tok| tok.kind == TokenKind::DocComment)
+        .map(|tok| tok.as_str())
+        .collect();
+    assert_eq!(docs, ["Adds one.", "Twice."]);
+
+    let options = Options {
+        emit: Emit::Ast,
+        ..Options::default()
+    };
+    match compile(source, &options) {
+        Ok(Output::Ast(ast)) => {
+            assert!(format!("{ast:?}").contains("doc: Some(\"Adds one.\\nTwice.\")"))
+        }
+        Ok(_) => panic!("expected an ast"),
+        Err(errors) => panic!("{errors:?}"),
+    }
+
+    let errors = match compile("fn main() {\n    /// stray\n    exit(1);\n}\n", &options) {
+        Ok(_) => panic!("expected errors"),
+        Err(errors) => errors,
+    };
+    assert_eq!(
+        errors[0].message,
+        "Doc comment must be followed by a 'fn' or 'let'"
+    );
+}
+
+#[test]
+fn lossless_tokens_rebuild_the_source() {
+    let mut sources = vec![
+        String::new(),
+        " \n// only trivia\n".to_string(),
+        "let $x = 'ab; */ /* open".to_string(),
+        "fn main() { /* a /* b */ */ exit(0x2A); } // done\r\n\n\t/// doc\nlet s: u8 = 'a';"
+            .to_string(),
+    ];
+    for entry in std::fs::read_dir("examples").unwrap() {
+        sources.push(std::fs::read_to_string(entry.unwrap().path()).unwrap());
+    }
+    for source in &sources {
+        // even with lex errors, bad input is kept in 'Error' tokens.
+        let (tokens, _) = Lexer::new(0, source).lossless(true).tokenize();
+        assert_eq!(tokens.back().unwrap().kind, TokenKind::Eof);
+        assert_eq!(lex::to_source(&tokens), *source);
+    }
+}
+
+#[test]
+fn lossless_trivia_ends_at_the_line() {
+    let source = "let x: i32 = 5; // five\n\n/* next */ exit(x);\n";
+    let tokens = lex_ok(Lexer::new(0, source).lossless(true));
+    let semi = tokens[6].trivia.as_deref().unwrap();
+    assert_eq!(semi.text, ";");
+    let trailing: Vec<(TriviaKind, &str)> = semi
+        .trailing
+        .iter()
+        .map(|piece| (piece.kind, piece.text.as_str()))
+        .collect();
+    assert_eq!(
+        trailing,
+        [
+            (TriviaKind::Whitespace, " "),
+            (TriviaKind::LineComment, "// five"),
+            (TriviaKind::Whitespace, "\n")
+        ]
+    );
+
+    let exit = tokens[7].trivia.as_deref().unwrap();
+    let leading: Vec<&str> = exit
+        .leading
+        .iter()
+        .map(|piece| piece.text.as_str())
+        .collect();
+    assert_eq!(leading, ["\n", "/* next */", " "]);
+
+    let tokens = lex_ok(Lexer::new(0, "fn main() {} // x\n").lossless(true));
+    assert!(Parser::new(tokens).parse_ast().is_ok());
+}
+
+#[test]
+fn keeps_unknown_chars_as_error_tokens() {
+    let (tokens, errors) = Lexer::new(0, "let x$: [ = 5\u{1};").tokenize();
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Unexpected character '$' (U+0024)",
+            "Unexpected character '[' (U+005B)",
+            "Unexpected character '\\u{1}' (U+0001)"
+        ]
+    );
+    let bad: Vec<(&str, u32)> = tokens
+        .iter()
+        .filter(|tok| tok.kind == TokenKind::Error)
+        .map(|tok| (tok.as_str(), tok.span.lo))
+        .collect();
+    assert_eq!(bad, [("$", 5), ("[", 8), ("\u{1}", 13)]);
+
+    // the parser carries on past them, without repeating the lexer's errors.
+    let source = "fn main() {\n    let x: i32 = 5 $;\n    let y: i32 = ;\n}\n";
+    let errors = match compile(source, &Options::default()) {
+        Ok(_) => panic!("expected errors"),
+        Err(errors) => errors,
+    };
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert_eq!(messages[0], "Unexpected character '$' (U+0024)");
+}
+
+#[test]
+fn lexer_streams_into_the_parser() {
+    let mut lexer = Lexer::new(0, "let x: i32 = 5 $;");
+    assert_eq!(lexer.next().map(|tok| tok.kind), Some(TokenKind::Let));
+    assert_eq!(
+        lexer.next().map(|tok| tok.as_str().to_string()),
+        Some("x".to_string())
+    );
+    assert!(lexer.take_errors().is_empty());
+    assert_eq!(lexer.by_ref().count(), 6);
+    assert_eq!(lexer.take_errors().len(), 1);
+
+    let source = "fn main() {\n    let x: i32 = 5;\n    exit(x);\n}\n".repeat(1000);
+    let mut lexer = Lexer::new(0, &source).lossless(true);
+    let ast = Parser::new(&mut lexer).parse_ast().unwrap();
+    assert_eq!(ast.stmts.len(), 1000);
+    assert!(lexer.take_errors().is_empty());
+}
+
+#[test]
+fn checks_negative_whitespace() {
+    let source = "  fn main() {\nexit(0);\n  }\n";
+    lex_ok(Lexer::new(0, source).negative_whitespace(true));
+
+    let source = "fn main() {\n    exit(0);\n}\n";
+    let errors = Lexer::new(0, source).negative_whitespace(true).tokenize().1;
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Indented by 0, expected 2",
+            "Indented by 4, expected 0",
+            "Indented by 0, expected 2"
+        ]
+    );
+
+    let errors = Lexer::new(0, "let Big: i32 = 1;")
+        .no_uppercase(true)
+        .tokenize()
+        .1;
+    assert_eq!(errors[0].message, "Uppercase identifier 'Big'");
+}
+
+#[test]
+fn lexes_loop_labels() {
+    let tokens = lex_ok(Lexer::new(0, "'outer: while 'a' { continue 'outer; }"));
+    let kinds: Vec<TokenKind> = tokens.iter().map(|tok| tok.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::Label,
+            TokenKind::Colon,
+            TokenKind::While,
+            TokenKind::CharLit,
+            TokenKind::OpenBrace,
+            TokenKind::Continue,
+            TokenKind::Label,
+            TokenKind::SemiColon,
+            TokenKind::CloseBrace
+        ]
+    );
+    assert_eq!(tokens[0].as_str(), "'outer");
+
+    let errors = Lexer::new(0, "'ab'").tokenize().1;
+    assert_eq!(
+        errors[0].message,
+        "Character literal may only contain one character"
+    );
+}
+
+#[test]
+fn lexes_underscores() {
+    let tokens = lex_ok(Lexer::new(0, "_foo __ a_1"));
+    let kinds: Vec<TokenKind> = tokens.iter().map(|tok| tok.kind).collect();
+    assert_eq!(kinds, [TokenKind::Ident; 3]);
+    assert_eq!(tokens[0].as_str(), "_foo");
+    assert_eq!(tokens[1].as_str(), "__");
+
+    let tokens = lex_ok(Lexer::new(0, "_ = x;"));
+    let kinds: Vec<TokenKind> = tokens.iter().map(|tok| tok.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::Underscore,
+            TokenKind::Eq,
+            TokenKind::Ident,
+            TokenKind::SemiColon
+        ]
+    );
+}
+
+#[test]
+fn checks_for_ranges() {
+    let tokens = lex_ok(Lexer::new(0, "for i in 0..=n step 2 {}"));
+    let kinds: Vec<TokenKind> = tokens.iter().map(|tok| tok.kind).collect();
+    assert_eq!(
+        kinds[..5],
+        [
+            TokenKind::For,
+            TokenKind::Ident,
+            TokenKind::In,
+            TokenKind::IntLit,
+            TokenKind::DotDotEq
+        ]
+    );
+
+    let error = |source: &str| match compile(source, &Options::default()) {
+        Ok(_) => panic!("expected errors"),
+        Err(errors) => errors[0].message.clone(),
+    };
+    assert_eq!(
+        error("fn main() { for i in 0..3 step 0 {} }"),
+        "A 'for' loop's step must be more than 0"
+    );
+    assert_eq!(
+        error("fn main() { for x in 0.0..1.0 {} }"),
+        "'for' loops count over integers, found 'float literal'"
+    );
+    assert_eq!(
+        error("fn main() { for i in 0..3 { i = 1; } }"),
+        "Re-assignment of a Constant: 'i'"
+    );
+}
+
+#[test]
+fn parses_compound_assignment() {
+    let ast = Parser::new(lex_ok(Lexer::new(0, "x *= a + b;")))
+        .parse_ast()
+        .unwrap();
+    match &ast.stmts[0] {
+        NodeStmt::CompoundAssign {
+            target: NodeExpr::Term(_),
+            op: TokenKind::Mul,
+            expr: NodeExpr::BinaryExpr {
+                op: TokenKind::Add, ..
+            },
+            ..
+        } => (),
+        stmt => panic!("expected 'x = x * (a + b)', found {stmt:?}"),
+    }
+
+    let ast = Parser::new(lex_ok(Lexer::new(0, "p^ = 5;"))).parse_ast();
+    assert!(matches!(
+        ast.unwrap().stmts[0],
+        NodeStmt::Assign {
+            target: NodeExpr::UnaryExpr {
+                op: TokenKind::Ptr,
+                ..
+            },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn parses_call_arguments() {
+    let call_args = |source: String| {
+        let ast = Parser::new(lex_ok(Lexer::new(0, &source))).parse_ast();
+        match ast.map(|ast| ast.stmts.into_iter().next()) {
+            Ok(Some(NodeStmt::Assign {
+                expr: NodeExpr::Term(NodeTerm::FnCall { args, .. }),
+                ..
+            })) => Ok(args.len()),
+            Ok(stmt) => panic!("expected a call, found {stmt:?}"),
+            Err(errors) => Err(errors[0].message.clone()),
+        }
+    };
+    for arity in 0..=6 {
+        let args: Vec<String> = (0..arity).map(|i| format!("a{i} + {i}")).collect();
+        let source = format!("x = f({});", args.join(", "));
+        assert_eq!(call_args(source), Ok(arity), "f({})", args.join(", "));
+        if arity > 0 {
+            let source = format!("x = f({},);", args.join(", "));
+            assert_eq!(call_args(source), Ok(arity), "trailing ',' with {arity}");
+        }
+    }
+
+    assert_eq!(
+        call_args("x = f(a b, c);".to_string()),
+        Err("expected 'CloseParen', found 'Ident'".to_string())
+    );
+    assert_eq!(
+        call_args("x = f(a,, b);".to_string()),
+        Err("Invalid Term, found 'Comma'".to_string())
+    );
+    assert_eq!(
+        call_args("x = (a, b);".to_string()),
+        Err("expected 'CloseParen', found 'Comma'".to_string())
+    );
+}
+
+#[test]
+fn resolves_ambiguous_overloads_in_signature_order() {
+    let called = |call: &str| {
+        let source = format!(
+            "fn f(x: i64) -> i64 {{\n    return x;\n}}\n\
+             fn f(x: i32) -> i32 {{\n    return x;\n}}\n\
+             fn main() {{\n    {call};\n}}\n"
+        );
+        match compile(&source, &Options::default()) {
+            Ok(Output::Asm(asm)) => asm
+                .lines()
+                .find(|line| line.contains("call f_"))
+                .map(str::trim)
+                .unwrap()
+                .to_string(),
+            Ok(_) => panic!("expected asm"),
+            Err(errors) => panic!("{errors:?}"),
+        }
+    };
+    // both take a literal, 'f(i32)' sorts first whichever is declared first.
+    assert_eq!(called("f(1)"), "call f_i32");
+    assert_eq!(called("f(3i16)"), "call f_i32");
+    assert_eq!(called("f(1i64)"), "call f_i64");
+}
+
+#[test]
+fn warns_on_unused_results() {
+    let warnings = |body: &str| {
+        let source = format!("fn f() -> i32 {{\n    return 1;\n}}\nfn main() {{\n{body}\n}}\n");
+        match compile_with_warnings(&source, &Options::default()) {
+            Ok((Output::Asm(_), warnings)) => warnings,
+            Ok(_) => panic!("expected asm"),
+            Err(errors) => panic!("{errors:?}"),
+        }
+    };
+    let unused = warnings("    let x: i32 = 1;\n    x + 1;");
+    assert_eq!(unused.len(), 1, "{unused:?}");
+    assert_eq!(unused[0].severity, Severity::Warning);
+    assert_eq!(
+        unused[0].message,
+        "Unused result, the expression has no effect"
+    );
+
+    // a call may have side effects, '_ =' throws a result away on purpose.
+    assert!(warnings("    f();\n    f() + 1;").is_empty());
+    assert!(warnings("    let x: i32 = 1;\n    _ = x + 1;").is_empty());
+
+    // warnings follow the errors when compiling fails.
+    let errors = match compile(
+        "fn main() {\n    1 + 1;\n    f();\n}\n",
+        &Options::default(),
+    ) {
+        Ok(_) => panic!("expected errors"),
+        Err(errors) => errors,
+    };
+    let severities: Vec<Severity> = errors.iter().map(|e| e.severity).collect();
+    assert_eq!(severities, [Severity::Error, Severity::Warning]);
+}
@@ -0,0 +1,176 @@
+// Golden-file tests over every program in 'examples/'.
+//  - each example starts with a header comment saying what should happen:
+//      - "// expect-exit: 100"       compiles, and the executable exits with 100.
+//      - "// expect-error: <text>"   fails to compile, with <text> in the diagnostic.
+//      - "// compile-flags: <flags>"  optional, extra flags passed to the compiler.
+//  - the generated asm of a compiling example is compared against 'tests/golden/<name>.asm'.
+//      - BLESS=1 cargo test  <- (re-)writes the golden files.
+//  - running the executables needs 'nasm' && 'ld', the test fails without them.
+//      - SKIP_RUN=1 cargo test  <- only compares the goldens, no exit codes are checked.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const COMPILER: &str = env!("CARGO_BIN_EXE_rust-compiler");
+const ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+#[derive(Debug)]
+enum Expect {
+    Exit(i32),
+    Error(String),
+}
+
+#[test]
+fn examples() {
+    let mut examples: Vec<PathBuf> = fs::read_dir(Path::new(ROOT).join("examples"))
+        .expect("examples directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "tc"))
+        .collect();
+    examples.sort();
+    assert!(!examples.is_empty(), "no examples found");
+
+    let can_link = match env::var("SKIP_RUN").as_deref() {
+        Ok("1") => false,
+        _ => {
+            let found = Command::new("nasm").arg("-v").output().is_ok();
+            assert!(
+                found,
+                "'nasm' not found, the exit codes can't be checked. \
+                 install nasm && ld, or set SKIP_RUN=1 to only compare the goldens"
+            );
+            true
+        }
+    };
+
+    let mut failures = Vec::new();
+    for example in &examples {
+        if let Err(e) = check_example(example, can_link) {
+            failures.push(format!("{}: {e}", example.display()));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} example(s) failed:\n\n{}",
+        failures.len(),
+        failures.join("\n\n")
+    );
+}
+
+fn check_example(example: &Path, can_link: bool) -> Result<(), String> {
+    let source = fs::read_to_string(example).map_err(|e| e.to_string())?;
+    let (expect, flags) = parse_header(&source)?;
+    let name = example.file_stem().unwrap().to_str().unwrap();
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("examples");
+    fs::create_dir_all(&out_dir).map_err(|e| e.to_string())?;
+
+    let asm_path = out_dir.join(format!("{name}.asm"));
+    let output = Command::new(COMPILER)
+        .arg(example)
+        .args(&flags)
+        .arg("--emit=asm")
+        .arg("-o")
+        .arg(&asm_path)
+        .output()
+        .map_err(|e| e.to_string())?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let code = match expect {
+        Expect::Error(msg) if output.status.success() => {
+            return Err(format!("expected error '{msg}', compiled successfully"))
+        }
+        Expect::Error(msg) if !stderr.contains(&msg) => {
+            return Err(format!("expected error '{msg}', found:\n{stderr}"))
+        }
+        Expect::Error(_) => return Ok(()),
+        Expect::Exit(_) if !output.status.success() => {
+            return Err(format!("failed to compile:\n{stderr}"))
+        }
+        Expect::Exit(code) => code,
+    };
+
+    let asm = fs::read_to_string(&asm_path).map_err(|e| e.to_string())?;
+    check_golden(name, &asm)?;
+    if !can_link {
+        return Ok(());
+    }
+
+    let exe_path = out_dir.join(name);
+    let output = Command::new(COMPILER)
+        .arg(example)
+        .args(&flags)
+        .arg("-o")
+        .arg(&exe_path)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("failed to link:\n{stderr}"));
+    }
+    let status = Command::new(&exe_path)
+        .status()
+        .map_err(|e| e.to_string())?;
+    match status.code() {
+        Some(found) if found == code => Ok(()),
+        found => Err(format!("expected exit code {code}, found {found:?}")),
+    }
+}
+
+// Reads the leading comment lines for an 'expect-*' header, and any 'compile-flags'.
+fn parse_header(source: &str) -> Result<(Expect, Vec<String>), String> {
+    let mut expect = None;
+    let mut flags = Vec::new();
+    for line in source.lines().take_while(|line| line.starts_with("//")) {
+        let line = line.trim_start_matches('/').trim();
+        if let Some(code) = line.strip_prefix("expect-exit:") {
+            expect = match code.trim().parse() {
+                Ok(code) => Some(Expect::Exit(code)),
+                Err(e) => return Err(format!("invalid exit code '{}': {e}", code.trim())),
+            };
+        } else if let Some(msg) = line.strip_prefix("expect-error:") {
+            expect = Some(Expect::Error(msg.trim().to_string()));
+        } else if let Some(line) = line.strip_prefix("compile-flags:") {
+            flags.extend(line.split_whitespace().map(str::to_string));
+        }
+    }
+    match expect {
+        Some(expect) => Ok((expect, flags)),
+        None => Err("missing '// expect-exit: N' or '// expect-error: MSG' header".to_string()),
+    }
+}
+
+fn check_golden(name: &str, asm: &str) -> Result<(), String> {
+    let golden_path = Path::new(ROOT)
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.asm"));
+    if env::var_os("BLESS").is_some() {
+        fs::create_dir_all(golden_path.parent().unwrap()).map_err(|e| e.to_string())?;
+        return fs::write(&golden_path, asm).map_err(|e| e.to_string());
+    }
+
+    let golden = match fs::read_to_string(&golden_path) {
+        Ok(golden) => golden,
+        Err(_) => {
+            return Err(format!(
+                "missing '{}', run with BLESS=1 to create it",
+                golden_path.display()
+            ))
+        }
+    };
+    if golden != asm {
+        let line = golden
+            .lines()
+            .zip(asm.lines())
+            .position(|(expected, found)| expected != found)
+            .unwrap_or(golden.lines().count().min(asm.lines().count()));
+        return Err(format!(
+            "asm differs from '{}' at line {}, run with BLESS=1 to update it",
+            golden_path.display(),
+            line + 1
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], 20 ; Ident('ans')
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("ans"), span: 86..89 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
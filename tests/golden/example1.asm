@@ -0,0 +1,40 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], 5 ; Ident('dwa')
+    mov eax, dword [rbp-4] ; Token { kind: Ident, value: Some("dwa"), span: 61..64 }
+    mov rcx, 5
+    add rax, rcx
+    mov dword [rbp-4], eax
+    mov eax, dword [rbp-4] ; Token { kind: Ident, value: Some("dwa"), span: 75..78 }
+    mov rcx, 5
+    mov rsi, 10
+    imul rcx, rsi
+    mov rsi, 3
+    mov rdi, 5
+    sub rsi, rdi
+    add rcx, rsi
+    mov rsi, 1
+    mov rdi, 5
+    sub rsi, rdi
+    add rcx, rsi
+    add rax, rcx
+    mov dword [rbp-4], eax
+; Exit Program
+    mov edi, dword [rbp-4] ; Token { kind: Ident, value: Some("dwa"), span: 117..120 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,20 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+; Exit Program
+    mov rdi, 2
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,75 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov byte [rbp-1], 65 ; Ident('c')
+    movzx rax, byte [rbp-1] ; Token { kind: Ident, value: Some("c"), span: 83..84 }
+    movzx rax, al
+    mov rcx, 1
+    add rax, rcx
+    movzx rax, al
+    mov byte [rbp-2], al ; Ident('next')
+    mov byte [rbp-3], 65 ; Ident('hex')
+    mov byte [rbp-4], 10 ; Ident('nl')
+; If
+; LogicalAnd
+; LogicalAnd
+    movzx rax, byte [rbp-2] ; Token { kind: Ident, value: Some("next"), span: 165..169 }
+    mov rcx, 66
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .2_AND_FALSE
+    movzx rcx, byte [rbp-3] ; Token { kind: Ident, value: Some("hex"), span: 180..183 }
+    movzx rsi, byte [rbp-1] ; Token { kind: Ident, value: Some("c"), span: 187..188 }
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    je .2_AND_FALSE
+    mov rax, 1
+    jmp .3_AND_END
+.2_AND_FALSE:
+    mov rax, 0
+.3_AND_END:
+    cmp rax, 0
+    je .4_AND_FALSE
+    movzx rcx, byte [rbp-4] ; Token { kind: Ident, value: Some("nl"), span: 192..194 }
+    movzx rcx, cl
+    mov rsi, 10
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    je .4_AND_FALSE
+    mov rax, 1
+    jmp .5_AND_END
+.4_AND_FALSE:
+    mov rax, 0
+.5_AND_END:
+    cmp rax, 0
+    je .1_IF_FALSE
+; Return
+    movzx rax, byte [rbp-2] ; Token { kind: Ident, value: Some("next"), span: 224..228 }
+    movzx rax, al
+    movsxd rax, eax
+    mov rax, rax
+    jmp .main_RETURN
+.1_IF_FALSE:
+; Return
+    mov rax, 0
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
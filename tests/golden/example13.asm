@@ -0,0 +1,101 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov rax, 65280
+    mov rcx, 3840
+    and rax, rcx
+    mov dword [rbp-4], eax ; Ident('mask')
+    mov word [rbp-6], 493 ; Ident('perms')
+    mov qword [rbp-14], 1000000 ; Ident('million')
+    mov rax, 128
+    neg rax
+    mov byte [rbp-15], al ; Ident('low')
+    mov byte [rbp-16], 255 ; Ident('high')
+; If
+; LogicalAnd
+; LogicalAnd
+; LogicalAnd
+    mov eax, dword [rbp-4] ; Token { kind: Ident, value: Some("mask"), span: 210..214 }
+    mov rcx, 3840
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .2_AND_FALSE
+    movzx rcx, word [rbp-6] ; Token { kind: Ident, value: Some("perms"), span: 226..231 }
+    mov rsi, 493
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    je .2_AND_FALSE
+    mov rax, 1
+    jmp .3_AND_END
+.2_AND_FALSE:
+    mov rax, 0
+.3_AND_END:
+    cmp rax, 0
+    je .4_AND_FALSE
+    mov rcx, qword [rbp-14] ; Token { kind: Ident, value: Some("million"), span: 242..249 }
+    mov rsi, 1000
+    push rax
+    mov rax, rcx
+    cqo
+    idiv rsi
+    mov rcx, rax
+    pop rax
+    mov rsi, 1000
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    je .4_AND_FALSE
+    mov rax, 1
+    jmp .5_AND_END
+.4_AND_FALSE:
+    mov rax, 0
+.5_AND_END:
+    cmp rax, 0
+    je .6_AND_FALSE
+    movzx rcx, byte [rbp-16] ; Token { kind: Ident, value: Some("high"), span: 269..273 }
+    movsxd rcx, ecx
+    mov rsi, 255
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    je .6_AND_FALSE
+    mov rax, 1
+    jmp .7_AND_END
+.6_AND_FALSE:
+    mov rax, 0
+.7_AND_END:
+    cmp rax, 0
+    je .1_IF_FALSE
+; Return
+    movsx rax, byte [rbp-15] ; Token { kind: Ident, value: Some("low"), span: 306..309 }
+    movsxd rax, eax
+    mov rcx, 170
+    add rax, rcx
+    mov rcx, 63
+    and rax, rcx
+    mov rax, rax
+    jmp .main_RETURN
+.1_IF_FALSE:
+; Return
+    mov rax, 0
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,181 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 64
+    mov rax, 4609434218613702656 ; 1.5
+    movq xmm0, rax
+    movsd qword [rbp-8], xmm0 ; Ident('a')
+    mov eax, 1074790400 ; 2.25
+    movd xmm0, eax
+    movss dword [rbp-12], xmm0 ; Ident('b')
+    movsd xmm0, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 103..104 }
+    mov rax, 4616189618054758400 ; 4.0
+    movq xmm1, rax
+    mulsd xmm0, xmm1
+    movss xmm1, dword [rbp-12] ; Token { kind: Ident, value: Some("b"), span: 113..114 }
+    cvtss2sd xmm1, xmm1
+    addsd xmm0, xmm1
+    movsd qword [rbp-20], xmm0 ; Ident('c')
+    mov rax, 4656510908468559872 ; 2000.0
+    movq xmm0, rax
+    movsd qword [rbp-28], xmm0 ; Ident('big')
+    movsd xmm0, qword [rbp-20] ; Token { kind: Ident, value: Some("c"), span: 167..168 }
+    movq rax, xmm0
+    btc rax, 63
+    movq xmm0, rax
+    movsd qword [rbp-36], xmm0 ; Ident('neg')
+    mov dword [rbp-40], 0 ; Ident('n')
+; If
+    movsd xmm0, qword [rbp-20] ; Token { kind: Ident, value: Some("c"), span: 201..202 }
+    mov rax, 4620833955170484224 ; 8.25
+    movq xmm1, rax
+    cmpeqsd xmm0, xmm1
+    movq rax, xmm0
+    and rax, 1
+    cmp rax, 0
+    je .1_IF_FALSE
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 225..226 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-40], eax
+.1_IF_FALSE:
+; If
+; LogicalAnd
+    movsd xmm0, qword [rbp-36] ; Token { kind: Ident, value: Some("neg"), span: 245..248 }
+    mov rax, 0 ; 0.0
+    movq xmm1, rax
+    cmpltsd xmm0, xmm1
+    movq rax, xmm0
+    and rax, 1
+    cmp rax, 0
+    je .3_AND_FALSE
+    movsd xmm0, qword [rbp-36] ; Token { kind: Ident, value: Some("neg"), span: 258..261 }
+    mov rcx, 4621256167635550208 ; 9.0
+    movq xmm1, rcx
+    movq rcx, xmm1
+    btc rcx, 63
+    movq xmm1, rcx
+    cmpltsd xmm1, xmm0
+    movq rcx, xmm1
+    and rcx, 1
+    cmp rcx, 0
+    je .3_AND_FALSE
+    mov rax, 1
+    jmp .4_AND_END
+.3_AND_FALSE:
+    mov rax, 0
+.4_AND_END:
+    cmp rax, 0
+    je .2_IF_FALSE
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 283..284 }
+    mov rcx, 2
+    add rax, rcx
+    mov dword [rbp-40], eax
+.2_IF_FALSE:
+; If
+; LogicalAnd
+    movss xmm0, dword [rbp-12] ; Token { kind: Ident, value: Some("b"), span: 303..304 }
+    mov eax, 1074790400 ; 2.25
+    movd xmm1, eax
+    cmpless xmm1, xmm0
+    movd eax, xmm1
+    and rax, 1
+    cmp rax, 0
+    je .6_AND_FALSE
+    movss xmm0, dword [rbp-12] ; Token { kind: Ident, value: Some("b"), span: 319..320 }
+    mov ecx, 1074790400 ; 2.25
+    movd xmm1, ecx
+    cmpless xmm0, xmm1
+    movd ecx, xmm0
+    and rcx, 1
+    cmp rcx, 0
+    je .6_AND_FALSE
+    mov rax, 1
+    jmp .7_AND_END
+.6_AND_FALSE:
+    mov rax, 0
+.7_AND_END:
+    cmp rax, 0
+    je .5_IF_FALSE
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 343..344 }
+    mov rcx, 4
+    add rax, rcx
+    mov dword [rbp-40], eax
+.5_IF_FALSE:
+; If
+    movsd xmm0, qword [rbp-28] ; Token { kind: Ident, value: Some("big"), span: 364..367 }
+    mov rax, 4636737291354636288 ; 100.0
+    movq xmm1, rax
+    divsd xmm0, xmm1
+    cvttsd2si rax, xmm0
+    movsxd rax, eax
+    mov rcx, 20
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .8_IF_FALSE
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 404..405 }
+    mov rcx, 8
+    add rax, rcx
+    mov dword [rbp-40], eax
+.8_IF_FALSE:
+    mov dword [rbp-44], 7 ; Ident('i')
+    movsxd rax, dword [rbp-44] ; Token { kind: Ident, value: Some("i"), span: 457..458 }
+    cvtsi2ss xmm0, rax
+    mov eax, 1073741824 ; 2.0
+    movd xmm1, eax
+    divss xmm0, xmm1
+    movss dword [rbp-48], xmm0 ; Ident('half')
+; If
+    movss xmm0, dword [rbp-48] ; Token { kind: Ident, value: Some("half"), span: 480..484 }
+    mov eax, 1080033280 ; 3.5
+    movd xmm1, eax
+    cmpeqss xmm0, xmm1
+    movd eax, xmm0
+    and rax, 1
+    cmp rax, 0
+    je .9_IF_FALSE
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 506..507 }
+    mov rcx, 16
+    add rax, rcx
+    mov dword [rbp-40], eax
+.9_IF_FALSE:
+    mov rax, 4613712638259704627 ; 2.9
+    movq xmm0, rax
+    movq rax, xmm0
+    btc rax, 63
+    movq xmm0, rax
+    cvttsd2si rax, xmm0
+    movsxd rax, eax
+    mov dword [rbp-52], eax ; Ident('truncated')
+; If
+    movsxd rax, dword [rbp-52] ; Token { kind: Ident, value: Some("truncated"), span: 565..574 }
+    mov rcx, 2
+    neg rcx
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .A_IF_FALSE
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 595..596 }
+    mov rcx, 32
+    add rax, rcx
+    mov dword [rbp-40], eax
+.A_IF_FALSE:
+; Return
+    movsxd rax, dword [rbp-40] ; Token { kind: Ident, value: Some("n"), span: 620..621 }
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
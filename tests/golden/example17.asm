@@ -0,0 +1,24 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov rax, 3
+    mov rcx, 4
+    add rax, rcx
+    mov dword [rbp-4], eax ; Ident('sum')
+; Return
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 174..177 }
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
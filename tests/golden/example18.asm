@@ -0,0 +1,35 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+unused_:
+    push rbp
+    mov rbp, rsp
+; Return
+    mov rax, 6
+    jmp .unused__RETURN
+.unused__RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov rax, 6
+    mov rcx, 2
+    imul rax, rcx
+    mov dword [rbp-4], eax ; Ident('ans')
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("ans"), span: 353..356 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
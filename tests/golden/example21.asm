@@ -0,0 +1,47 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], 1 ; Ident('count')
+; If
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 214..219 }
+    mov rcx, 1
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .1_IF_FALSE
+; While
+    jmp .2_WHILE_CMP
+.3_WHILE_SCOPE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 247..252 }
+    mov rcx, 2
+    imul rax, rcx
+    mov dword [rbp-4], eax
+.2_WHILE_CMP:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 235..240 }
+    mov rcx, 8
+    cmp rax, rcx
+    setl al
+    movzx rax, al
+    cmp rax, 0
+    jne .3_WHILE_SCOPE
+.4_WHILE_END:
+.1_IF_FALSE:
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 278..283 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,227 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov dword [rbp-4], 0 ; Ident('sum')
+; For
+    mov rax, 0
+    mov dword [rbp-8], 10
+    mov dword [rbp-12], eax ; Ident('i')
+    movsxd rax, dword [rbp-12]
+    movsxd rcx, dword [rbp-8]
+    cmp rax, rcx
+    jge .3_FOR_END
+.1_FOR_SCOPE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 177..180 }
+    movsxd rcx, dword [rbp-12] ; Token { kind: Ident, value: Some("i"), span: 183..184 }
+    add rax, rcx
+    mov dword [rbp-4], eax
+.2_FOR_NEXT:
+    movsxd rax, dword [rbp-12]
+    movsxd rcx, dword [rbp-8]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .3_FOR_END
+    add rax, rsi
+    mov dword [rbp-12], eax
+    jmp .1_FOR_SCOPE
+.3_FOR_END:
+    mov dword [rbp-8], 4 ; Ident('n')
+; For
+    mov rax, 1
+    movsxd rcx, dword [rbp-8] ; Token { kind: Ident, value: Some("n"), span: 230..231 }
+    mov dword [rbp-12], ecx
+    mov dword [rbp-16], eax ; Ident('i')
+    movsxd rax, dword [rbp-16]
+    movsxd rcx, dword [rbp-12]
+    cmp rax, rcx
+    jg .6_FOR_END
+.4_FOR_SCOPE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 248..251 }
+    movsxd rcx, dword [rbp-16] ; Token { kind: Ident, value: Some("i"), span: 254..255 }
+    add rax, rcx
+    mov dword [rbp-4], eax
+.5_FOR_NEXT:
+    movsxd rax, dword [rbp-16]
+    movsxd rcx, dword [rbp-12]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jb .6_FOR_END
+    add rax, rsi
+    mov dword [rbp-16], eax
+    jmp .4_FOR_SCOPE
+.6_FOR_END:
+; For
+    mov rax, 0
+    mov dword [rbp-12], 10
+    mov dword [rbp-16], eax ; Ident('i')
+    movsxd rax, dword [rbp-16]
+    movsxd rcx, dword [rbp-12]
+    cmp rax, rcx
+    jge .9_FOR_END
+.7_FOR_SCOPE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 306..309 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.8_FOR_NEXT:
+    movsxd rax, dword [rbp-16]
+    movsxd rcx, dword [rbp-12]
+    mov rsi, 3
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .9_FOR_END
+    add rax, rsi
+    mov dword [rbp-16], eax
+    jmp .7_FOR_SCOPE
+.9_FOR_END:
+    mov dword [rbp-12], 0 ; Ident('count')
+; For
+    mov rax, 0
+    mov byte [rbp-13], 255
+    mov byte [rbp-14], al ; Ident('byte')
+    movzx rax, byte [rbp-14]
+    movzx rcx, byte [rbp-13]
+    cmp rax, rcx
+    ja .C_FOR_END
+.A_FOR_SCOPE:
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("count"), span: 456..461 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-12], eax
+.B_FOR_NEXT:
+    movzx rax, byte [rbp-14]
+    movzx rcx, byte [rbp-13]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jb .C_FOR_END
+    add rax, rsi
+    mov byte [rbp-14], al
+    jmp .A_FOR_SCOPE
+.C_FOR_END:
+; If
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("count"), span: 480..485 }
+    mov rcx, 256
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .D_IF_FALSE
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 509..512 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.D_IF_FALSE:
+; For
+    mov rax, 5
+    mov dword [rbp-16], 5
+    mov dword [rbp-20], eax ; Ident('i')
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    cmp rax, rcx
+    jge .10_FOR_END
+.E_FOR_SCOPE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 559..562 }
+    mov rcx, 100
+    add rax, rcx
+    mov dword [rbp-4], eax
+.F_FOR_NEXT:
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .10_FOR_END
+    add rax, rsi
+    mov dword [rbp-20], eax
+    jmp .E_FOR_SCOPE
+.10_FOR_END:
+; For
+    mov rax, 0
+    mov dword [rbp-16], 100
+    mov dword [rbp-20], eax ; Ident('i')
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    cmp rax, rcx
+    jge .13_FOR_END
+.11_FOR_SCOPE:
+; If
+    movsxd rax, dword [rbp-20] ; Token { kind: Ident, value: Some("i"), span: 610..611 }
+    mov rcx, 3
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .14_IF_FALSE
+    jmp .13_FOR_END ; break
+.14_IF_FALSE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 662..665 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.12_FOR_NEXT:
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .13_FOR_END
+    add rax, rsi
+    mov dword [rbp-20], eax
+    jmp .11_FOR_SCOPE
+.13_FOR_END:
+    mov dword [rbp-16], 2 ; Ident('stride')
+; For
+    mov rax, 4
+    neg rax
+    mov dword [rbp-20], 4
+    movsxd rcx, dword [rbp-16] ; Token { kind: Ident, value: Some("stride"), span: 727..733 }
+    mov dword [rbp-24], ecx
+    mov dword [rbp-28], eax ; Ident('i')
+    movsxd rax, dword [rbp-28]
+    movsxd rcx, dword [rbp-20]
+    cmp rax, rcx
+    jge .17_FOR_END
+    movsxd rsi, dword [rbp-24]
+    cmp rsi, 0
+    jg .18_FOR_STEP_OK
+    mov rdi, 101 ; 'for' step isn't more than 0
+    mov rax, 60
+    syscall
+.18_FOR_STEP_OK:
+.15_FOR_SCOPE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 750..753 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.16_FOR_NEXT:
+    movsxd rax, dword [rbp-28]
+    movsxd rcx, dword [rbp-20]
+    movsxd rsi, dword [rbp-24]
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .17_FOR_END
+    add rax, rsi
+    mov dword [rbp-28], eax
+    jmp .15_FOR_SCOPE
+.17_FOR_END:
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("sum"), span: 775..778 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,241 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov dword [rbp-4], 0 ; Ident('count')
+    mov dword [rbp-8], 0 ; Ident('i')
+; While
+    jmp .1_WHILE_CMP
+.2_WHILE_SCOPE:
+    mov dword [rbp-12], 0 ; Ident('j')
+; While
+    jmp .4_WHILE_CMP
+.5_WHILE_SCOPE:
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("j"), span: 360..361 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-12], eax
+.4_WHILE_CMP:
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("j"), span: 336..337 }
+    mov rcx, 3
+    cmp rax, rcx
+    setl al
+    movzx rax, al
+    cmp rax, 0
+    jne .5_WHILE_SCOPE
+.6_WHILE_END:
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("i"), span: 389..390 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-8], eax
+; If
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("i"), span: 407..408 }
+    mov rcx, 2
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .7_IF_FALSE
+    jmp .3_WHILE_END ; break
+.7_IF_FALSE:
+.1_WHILE_CMP:
+    mov rax, 1
+    cmp rax, 0
+    jne .2_WHILE_SCOPE
+.3_WHILE_END:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 463..468 }
+    movsxd rcx, dword [rbp-8] ; Token { kind: Ident, value: Some("i"), span: 471..472 }
+    add rax, rcx
+    mov dword [rbp-4], eax
+; For
+    mov rax, 0
+    mov dword [rbp-12], 10
+    mov dword [rbp-16], eax ; Ident('n')
+    movsxd rax, dword [rbp-16]
+    movsxd rcx, dword [rbp-12]
+    cmp rax, rcx
+    jge .A_FOR_END
+.8_FOR_SCOPE:
+; If
+    movsxd rax, dword [rbp-16] ; Token { kind: Ident, value: Some("n"), span: 507..508 }
+    mov rcx, 2
+    cqo
+    idiv rcx
+    mov rax, rdx
+    mov rcx, 0
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .B_IF_FALSE
+    jmp .9_FOR_NEXT ; continue
+.B_IF_FALSE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 568..573 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.9_FOR_NEXT:
+    movsxd rax, dword [rbp-16]
+    movsxd rcx, dword [rbp-12]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .A_FOR_END
+    add rax, rsi
+    mov dword [rbp-16], eax
+    jmp .8_FOR_SCOPE
+.A_FOR_END:
+    mov dword [rbp-12], 0 ; Ident('k')
+; While
+    jmp .C_WHILE_CMP
+.D_WHILE_SCOPE:
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("k"), span: 640..641 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-12], eax
+; If
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("k"), span: 658..659 }
+    mov rcx, 3
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .F_IF_FALSE
+    jmp .C_WHILE_CMP ; continue
+.F_IF_FALSE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 715..720 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.C_WHILE_CMP:
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("k"), span: 620..621 }
+    mov rcx, 5
+    cmp rax, rcx
+    setl al
+    movzx rax, al
+    cmp rax, 0
+    jne .D_WHILE_SCOPE
+.E_WHILE_END:
+; For
+    mov rax, 0
+    mov dword [rbp-16], 10
+    mov dword [rbp-20], eax ; Ident('a')
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    cmp rax, rcx
+    jge .12_FOR_END
+.10_FOR_SCOPE:
+; For
+    mov rax, 0
+    mov dword [rbp-24], 10
+    mov dword [rbp-28], eax ; Ident('b')
+    movsxd rax, dword [rbp-28]
+    movsxd rcx, dword [rbp-24]
+    cmp rax, rcx
+    jge .15_FOR_END
+.13_FOR_SCOPE:
+; If
+    movsxd rax, dword [rbp-28] ; Token { kind: Ident, value: Some("b"), span: 802..803 }
+    mov rcx, 3
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .16_IF_FALSE
+    jmp .11_FOR_NEXT ; continue
+.16_IF_FALSE:
+; If
+    movsxd rax, dword [rbp-20] ; Token { kind: Ident, value: Some("a"), span: 873..874 }
+    mov rcx, 4
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .17_IF_FALSE
+    jmp .12_FOR_END ; break
+.17_IF_FALSE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 946..951 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+.14_FOR_NEXT:
+    movsxd rax, dword [rbp-28]
+    movsxd rcx, dword [rbp-24]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .15_FOR_END
+    add rax, rsi
+    mov dword [rbp-28], eax
+    jmp .13_FOR_SCOPE
+.15_FOR_END:
+.11_FOR_NEXT:
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .12_FOR_END
+    add rax, rsi
+    mov dword [rbp-20], eax
+    jmp .10_FOR_SCOPE
+.12_FOR_END:
+; While
+    jmp .18_WHILE_CMP
+.19_WHILE_SCOPE:
+; For
+    mov rax, 0
+    mov dword [rbp-16], 5
+    mov dword [rbp-20], eax ; Ident('c')
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    cmp rax, rcx
+    jge .1D_FOR_END
+.1B_FOR_SCOPE:
+; If
+    movsxd rax, dword [rbp-20] ; Token { kind: Ident, value: Some("c"), span: 1037..1038 }
+    mov rcx, 2
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .1E_IF_FALSE
+    jmp .1A_WHILE_END ; break
+.1E_IF_FALSE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 1109..1114 }
+    mov rcx, 10
+    add rax, rcx
+    mov dword [rbp-4], eax
+.1C_FOR_NEXT:
+    movsxd rax, dword [rbp-20]
+    movsxd rcx, dword [rbp-16]
+    mov rsi, 1
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .1D_FOR_END
+    add rax, rsi
+    mov dword [rbp-20], eax
+    jmp .1B_FOR_SCOPE
+.1D_FOR_END:
+.18_WHILE_CMP:
+    mov rax, 1
+    cmp rax, 0
+    jne .19_WHILE_SCOPE
+.1A_WHILE_END:
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("count"), span: 1147..1152 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,87 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov dword [rbp-4], 2 ; Ident('x')
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("x"), span: 148..149 }
+    mov rcx, 3
+    mov rsi, 4
+    add rcx, rsi
+    imul rax, rcx
+    mov dword [rbp-4], eax
+    mov dword [rbp-8], 100 ; Ident('y')
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("y"), span: 191..192 }
+    mov rcx, 10
+    mov rsi, 4
+    sub rcx, rsi
+    sub rax, rcx
+    mov dword [rbp-8], eax
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("y"), span: 208..209 }
+    mov rcx, 2
+    mov rsi, 3
+    add rcx, rsi
+    cqo
+    idiv rcx
+    mov dword [rbp-8], eax
+    mov rax, 20
+    neg rax
+    mov dword [rbp-12], eax ; Ident('z')
+    lea rax, [rbp-12]
+    mov qword [rbp-20], rax ; Ident('p')
+    mov rax, qword [rbp-20]
+    mov rcx, 8
+    neg rcx
+    mov dword [rax], ecx
+    mov rax, qword [rbp-20]
+    movsxd rcx, dword [rax]
+    mov rsi, 2
+    push rax
+    mov rax, rcx
+    cqo
+    idiv rsi
+    mov rcx, rax
+    pop rax
+    mov dword [rax], ecx
+    mov rax, qword [rbp-20]
+    movsxd rcx, dword [rax]
+    movsxd rsi, dword [rbp-4] ; Token { kind: Ident, value: Some("x"), span: 305..306 }
+    add rcx, rsi
+    mov dword [rax], ecx
+    mov byte [rbp-21], 250 ; Ident('b')
+    lea rax, [rbp-21]
+    mov qword [rbp-29], rax ; Ident('q')
+    mov rax, qword [rbp-29]
+    movzx rcx, byte [rax]
+    mov rsi, 240
+    sub rcx, rsi
+    mov byte [rax], cl
+    mov rax, qword [rbp-29]
+    movzx rcx, byte [rax]
+    mov rsi, 5
+    or rcx, rsi
+    mov byte [rax], cl
+; Exit Program
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("x"), span: 393..394 }
+    movsxd rcx, dword [rbp-8] ; Token { kind: Ident, value: Some("y"), span: 397..398 }
+    add rax, rcx
+    movsxd rcx, dword [rbp-12] ; Token { kind: Ident, value: Some("z"), span: 401..402 }
+    add rax, rcx
+    movzx rcx, byte [rbp-21] ; Token { kind: Ident, value: Some("b"), span: 405..406 }
+    movsxd rcx, ecx
+    add rax, rcx
+    mov rdi, rax
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
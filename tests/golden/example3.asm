@@ -0,0 +1,26 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov rax, 10
+    mov rcx, 5
+    cqo
+    idiv rcx
+    mov dword [rbp-4], eax ; Ident('x')
+; Exit Program
+    mov edi, dword [rbp-4] ; Token { kind: Ident, value: Some("x"), span: 64..65 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
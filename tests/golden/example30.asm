@@ -0,0 +1,246 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+add_i32_i32:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], edi
+    mov dword [rbp-8], esi
+; Return
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("a"), span: 143..144 }
+    movsxd rcx, dword [rbp-8] ; Token { kind: Ident, value: Some("b"), span: 147..148 }
+    add rax, rcx
+    mov rax, rax
+    jmp .add_i32_i32_RETURN
+.add_i32_i32_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+add_f64_f64:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    movsd qword [rbp-8], xmm0
+    movsd qword [rbp-16], xmm1
+; Return
+    movsd xmm0, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 196..197 }
+    movsd xmm1, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 200..201 }
+    addsd xmm0, xmm1
+    jmp .add_f64_f64_RETURN
+.add_f64_f64_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+expect_i32_i32:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], edi
+    mov dword [rbp-8], esi
+; If
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("x"), span: 244..245 }
+    movsxd rcx, dword [rbp-8] ; Token { kind: Ident, value: Some("want"), span: 249..253 }
+    cmp rax, rcx
+    setne al
+    movzx rax, al
+    cmp rax, 0
+    je .1_IF_FALSE
+; Exit Program
+    mov rdi, 1
+    mov rax, 60
+    syscall
+.1_IF_FALSE:
+.expect_i32_i32_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+six_i64_i64_i64_u8_i16_i32:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov qword [rbp-8], rdi
+    mov qword [rbp-16], rsi
+    mov qword [rbp-24], rdx
+    mov byte [rbp-25], cl
+    mov word [rbp-27], r8w
+    mov dword [rbp-31], r9d
+; Return
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 356..357 }
+    mov rcx, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 360..361 }
+    add rax, rcx
+    mov rcx, qword [rbp-24] ; Token { kind: Ident, value: Some("c"), span: 364..365 }
+    add rax, rcx
+    movzx rcx, byte [rbp-25] ; Token { kind: Ident, value: Some("d"), span: 368..369 }
+    add rax, rcx
+    movsx rcx, word [rbp-27] ; Token { kind: Ident, value: Some("e"), span: 379..380 }
+    add rax, rcx
+    movsxd rcx, dword [rbp-31] ; Token { kind: Ident, value: Some("f"), span: 390..391 }
+    add rax, rcx
+    mov rax, rax
+    jmp .six_i64_i64_i64_u8_i16_i32_RETURN
+.six_i64_i64_i64_u8_i16_i32_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov dword [rbp-4], 2 ; Ident('n')
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("n"), span: 446..447 }
+    push rax
+    mov rax, 2
+    push rax
+    pop rsi
+    pop rdi
+    call expect_i32_i32
+    mov rax, 1
+    push rax
+    mov rax, 2
+    push rax
+    pop rsi
+    pop rdi
+    call add_i32_i32
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("n"), span: 493..494 }
+    push rax
+    mov rax, 3
+    push rax
+    mov rax, 4
+    push rax
+    pop rsi
+    pop rdi
+    call add_i32_i32
+    push rax
+    pop rsi
+    pop rdi
+    call add_i32_i32
+    mov rcx, 2
+    imul rax, rcx
+    push rax
+    mov rcx, 1
+    push rcx
+    mov rcx, 1
+    push rcx
+    pop rsi
+    pop rdi
+    call add_i32_i32
+    mov rcx, rax
+    pop rax
+    add rax, rcx
+    mov dword [rbp-8], eax ; Ident('x')
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("x"), span: 535..536 }
+    push rax
+    mov rax, 20
+    push rax
+    pop rsi
+    pop rdi
+    call expect_i32_i32
+    mov rax, 4609434218613702656 ; 1.5
+    movq xmm0, rax
+    sub rsp, 8
+    movsd qword [rsp], xmm0
+    mov rax, 4612811918334230528 ; 2.5
+    movq xmm0, rax
+    sub rsp, 8
+    movsd qword [rsp], xmm0
+    movsd xmm1, qword [rsp]
+    add rsp, 8
+    movsd xmm0, qword [rsp]
+    add rsp, 8
+    call add_f64_f64
+    sub rsp, 8
+    movq qword [rsp], xmm0
+    mov rax, 4602678819172646912 ; 0.5
+    movq xmm1, rax
+    sub rsp, 8
+    movsd qword [rsp], xmm1
+    mov rax, 4602678819172646912 ; 0.5
+    movq xmm1, rax
+    sub rsp, 8
+    movsd qword [rsp], xmm1
+    movsd xmm1, qword [rsp]
+    add rsp, 8
+    movsd xmm0, qword [rsp]
+    add rsp, 8
+    call add_f64_f64
+    movsd xmm1, xmm0
+    movq xmm0, qword [rsp]
+    add rsp, 8
+    mulsd xmm0, xmm1
+    movsd qword [rbp-16], xmm0 ; Ident('f')
+    movsd xmm0, qword [rbp-16] ; Token { kind: Ident, value: Some("f"), span: 602..603 }
+    cvttsd2si rax, xmm0
+    movsxd rax, eax
+    push rax
+    mov rax, 4
+    push rax
+    pop rsi
+    pop rdi
+    call expect_i32_i32
+    mov rax, 1
+    push rax
+    mov rax, 2
+    push rax
+    mov rax, 3
+    push rax
+    mov rax, 4
+    push rax
+    mov rax, 5
+    neg rax
+    push rax
+    mov rax, 3
+    push rax
+    mov rax, 3
+    push rax
+    pop rsi
+    pop rdi
+    call add_i32_i32
+    push rax
+    pop r9
+    pop r8
+    pop rcx
+    pop rdx
+    pop rsi
+    pop rdi
+    call six_i64_i64_i64_u8_i16_i32
+    mov qword [rbp-24], rax ; Ident('s')
+    mov rax, qword [rbp-24] ; Token { kind: Ident, value: Some("s"), span: 676..677 }
+    movsxd rax, eax
+    push rax
+    mov rax, 11
+    push rax
+    pop rsi
+    pop rdi
+    call expect_i32_i32
+; Exit Program
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("x"), span: 701..702 }
+    movsd xmm0, qword [rbp-16] ; Token { kind: Ident, value: Some("f"), span: 705..706 }
+    cvttsd2si rcx, xmm0
+    movsxd rcx, ecx
+    add rax, rcx
+    push rax
+    mov rcx, qword [rbp-24] ; Token { kind: Ident, value: Some("s"), span: 720..721 }
+    movsxd rcx, ecx
+    push rcx
+    mov rcx, 0
+    push rcx
+    pop rsi
+    pop rdi
+    call add_i32_i32
+    mov rcx, rax
+    pop rax
+    add rax, rcx
+    mov rdi, rax
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
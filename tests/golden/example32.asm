@@ -0,0 +1,55 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov rax, 281474976710655
+    mov qword [rbp-8], rax ; Ident('mask')
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("mask"), span: 96..100 }
+    mov rcx, 32
+    sar rax, rcx
+    mov qword [rbp-16], rax ; Ident('high')
+; If
+; LogicalAnd
+    mov rax, qword [rbp-16] ; Token { kind: Ident, value: Some("high"), span: 118..122 }
+    mov rcx, 65535
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .2_AND_FALSE
+    mov rcx, qword [rbp-8] ; Token { kind: Ident, value: Some("mask"), span: 139..143 }
+    mov rsi, 1
+    add rcx, rsi
+    mov rsi, 281474976710656
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    je .2_AND_FALSE
+    mov rax, 1
+    jmp .3_AND_END
+.2_AND_FALSE:
+    mov rax, 0
+.3_AND_END:
+    cmp rax, 0
+    je .1_IF_FALSE
+; Return
+    mov rax, 16
+    jmp .main_RETURN
+.1_IF_FALSE:
+; Return
+    mov rax, 0
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
@@ -0,0 +1,62 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov dword [rbp-4], 9 ; Ident('n')
+    lea rax, [rbp-4]
+    mov qword [rbp-12], rax ; Ident('p')
+    mov rax, qword [rbp-12]
+    movsxd rcx, dword [rax]
+    mov rsi, 1
+    add rcx, rsi
+    mov dword [rax], ecx
+    mov rax, qword [rbp-12]
+    movsxd rcx, dword [rax]
+    mov rsi, qword [rbp-12] ; Token { kind: Ident, value: Some("p"), span: 176..177 }
+    movsxd rsi, dword [rsi]
+    imul rcx, rsi
+    mov dword [rax], ecx
+    mov rax, 4609434218613702656 ; 1.5
+    movq xmm0, rax
+    movsd qword [rbp-20], xmm0 ; Ident('f')
+    lea rax, [rbp-20]
+    mov qword [rbp-28], rax ; Ident('q')
+    mov rax, qword [rbp-28]
+    movsd xmm0, qword [rax]
+    mov rcx, 4611686018427387904 ; 2.0
+    movq xmm1, rcx
+    mulsd xmm0, xmm1
+    movsd qword [rax], xmm0
+    mov rax, qword [rbp-28]
+    movsd xmm0, qword [rax]
+    mov rcx, 4602678819172646912 ; 0.5
+    movq xmm1, rcx
+    subsd xmm0, xmm1
+    movsd qword [rax], xmm0
+; Exit Program
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("n"), span: 269..270 }
+    mov rcx, 5
+    cqo
+    idiv rcx
+    movsd xmm0, qword [rbp-20] ; Token { kind: Ident, value: Some("f"), span: 277..278 }
+    cvttsd2si rcx, xmm0
+    movsxd rcx, ecx
+    add rax, rcx
+    mov rcx, 1
+    add rax, rcx
+    mov rdi, rax
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
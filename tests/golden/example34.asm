@@ -0,0 +1,88 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov byte [rbp-1], 0 ; Ident('stride')
+; For
+    mov rax, 5
+    mov byte [rbp-2], 5
+    movzx rcx, byte [rbp-1] ; Token { kind: Ident, value: Some("stride"), span: 177..183 }
+    mov byte [rbp-3], cl
+    mov byte [rbp-4], al ; Ident('i')
+    movzx rax, byte [rbp-4]
+    movzx rcx, byte [rbp-2]
+    cmp rax, rcx
+    jae .3_FOR_END
+    movzx rsi, byte [rbp-3]
+    cmp rsi, 0
+    ja .4_FOR_STEP_OK
+    mov rdi, 101 ; 'for' step isn't more than 0
+    mov rax, 60
+    syscall
+.4_FOR_STEP_OK:
+.1_FOR_SCOPE:
+; Exit Program
+    mov rdi, 1
+    mov rax, 60
+    syscall
+.2_FOR_NEXT:
+    movzx rax, byte [rbp-4]
+    movzx rcx, byte [rbp-2]
+    movzx rsi, byte [rbp-3]
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .3_FOR_END
+    add rax, rsi
+    mov byte [rbp-4], al
+    jmp .1_FOR_SCOPE
+.3_FOR_END:
+; For
+    mov rax, 0
+    mov byte [rbp-2], 3
+    movzx rcx, byte [rbp-1] ; Token { kind: Ident, value: Some("stride"), span: 236..242 }
+    mov byte [rbp-3], cl
+    mov byte [rbp-4], al ; Ident('i')
+    movzx rax, byte [rbp-4]
+    movzx rcx, byte [rbp-2]
+    cmp rax, rcx
+    jae .7_FOR_END
+    movzx rsi, byte [rbp-3]
+    cmp rsi, 0
+    ja .8_FOR_STEP_OK
+    mov rdi, 101 ; 'for' step isn't more than 0
+    mov rax, 60
+    syscall
+.8_FOR_STEP_OK:
+.5_FOR_SCOPE:
+; Exit Program
+    mov rdi, 2
+    mov rax, 60
+    syscall
+.6_FOR_NEXT:
+    movzx rax, byte [rbp-4]
+    movzx rcx, byte [rbp-2]
+    movzx rsi, byte [rbp-3]
+    sub rcx, rax
+    cmp rcx, rsi
+    jbe .7_FOR_END
+    add rax, rsi
+    mov byte [rbp-4], al
+    jmp .5_FOR_SCOPE
+.7_FOR_END:
+; Exit Program
+    mov rdi, 3
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
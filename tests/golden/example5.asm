@@ -0,0 +1,81 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], 0 ; Ident('ans')
+    mov dword [rbp-8], 0 ; Ident('i')
+    mov dword [rbp-12], 100 ; Ident('break_lexer123')
+; While
+    jmp .1_WHILE_CMP
+.2_WHILE_SCOPE:
+; If
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("i"), span: 151..152 }
+    mov rcx, 10
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .4_IF_FALSE
+    jmp .3_WHILE_END ; break
+.4_IF_FALSE:
+    mov dword [rbp-16], 0 ; Ident('j')
+; While
+    jmp .5_WHILE_CMP
+.6_WHILE_SCOPE:
+; If
+    movsxd rax, dword [rbp-16] ; Token { kind: Ident, value: Some("j"), span: 257..258 }
+    mov rcx, 10
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .8_IF_FALSE
+    jmp .7_WHILE_END ; break
+.8_IF_FALSE:
+    movsxd rax, dword [rbp-4] ; Token { kind: Ident, value: Some("ans"), span: 323..326 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-4], eax
+    movsxd rax, dword [rbp-16] ; Token { kind: Ident, value: Some("j"), span: 348..349 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-16], eax
+.5_WHILE_CMP:
+    mov rax, 1
+    mov rcx, 1
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    jne .6_WHILE_SCOPE
+.7_WHILE_END:
+    movsxd rax, dword [rbp-8] ; Token { kind: Ident, value: Some("i"), span: 377..378 }
+    mov rcx, 1
+    add rax, rcx
+    mov dword [rbp-8], eax
+.1_WHILE_CMP:
+    mov rax, 1
+    mov rcx, 1
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    jne .2_WHILE_SCOPE
+.3_WHILE_END:
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("ans"), span: 400..403 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
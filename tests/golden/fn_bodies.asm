@@ -0,0 +1,38 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+nothing_i64:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov qword [rbp-8], rdi
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("x"), span: 136..137 }
+    mov rcx, 1
+    add rax, rcx
+    mov qword [rbp-16], rax ; Ident('y')
+.nothing_i64_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov qword [rbp-8], 5 ; Ident('a')
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 202..203 }
+    mov rcx, 7
+    add rax, rcx
+    mov qword [rbp-16], rax ; Ident('b')
+; Return
+    mov rax, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 220..221 }
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
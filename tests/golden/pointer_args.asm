@@ -0,0 +1,51 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, 0
+    mov rax, 60
+    syscall
+bump_i32_i32:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov qword [rbp-8], rdi
+    mov dword [rbp-12], esi
+    mov rax, qword [rbp-8]
+    movsxd rcx, dword [rax]
+    movsxd rsi, dword [rbp-12] ; Token { kind: Ident, value: Some("by"), span: 149..151 }
+    add rcx, rsi
+    mov dword [rax], ecx
+.bump_i32_i32_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov dword [rbp-4], 11 ; Ident('n')
+    lea rax, [rbp-4]
+    push rax
+    mov rax, 4
+    push rax
+    pop rsi
+    pop rdi
+    call bump_i32_i32
+    lea rax, [rbp-4]
+    push rax
+    mov rax, 4
+    push rax
+    pop rsi
+    pop rdi
+    call bump_i32_i32
+; Exit Program
+    movsxd rdi, dword [rbp-4] ; Token { kind: Ident, value: Some("n"), span: 236..237 }
+    mov rax, 60
+    syscall
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
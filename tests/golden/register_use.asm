@@ -0,0 +1,134 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 32
+    mov qword [rbp-8], 10 ; Ident('a')
+    mov qword [rbp-16], 3 ; Ident('b')
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 183..184 }
+    mov rcx, 60
+    mov rsi, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 192..193 }
+    push rax
+    mov rax, rcx
+    cqo
+    idiv rsi
+    mov rcx, rax
+    pop rax
+    add rax, rcx
+    mov rcx, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 196..197 }
+    mov rsi, 2
+    push rax
+    mov rax, rcx
+    cqo
+    idiv rsi
+    mov rcx, rdx
+    pop rax
+    add rax, rcx
+    mov qword [rbp-24], rax ; Ident('ans')
+; If
+; LogicalAnd
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 212..213 }
+    mov rcx, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 216..217 }
+    cmp rax, rcx
+    setl al
+    movzx rax, al
+    test rax, rax
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .4_AND_FALSE
+; CmpOr
+    mov rcx, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 223..224 }
+    mov rsi, 4
+    cmp rcx, rsi
+    sete cl
+    movzx rcx, cl
+    cmp rcx, 0
+    jne .2_OR_TRUE
+    mov rsi, 1
+    cmp rsi, 0
+    jne .2_OR_TRUE
+    mov rcx, 0
+    jmp .3_OR_END
+.2_OR_TRUE:
+    mov rcx, 1
+.3_OR_END:
+    cmp rcx, 0
+    je .4_AND_FALSE
+    mov rax, 1
+    jmp .5_AND_END
+.4_AND_FALSE:
+    mov rax, 0
+.5_AND_END:
+    cmp rax, 0
+    je .1_IF_FALSE
+    mov rax, qword [rbp-24] ; Token { kind: Ident, value: Some("ans"), span: 255..258 }
+    mov rcx, 10
+    add rax, rcx
+    mov qword [rbp-24], rax
+.1_IF_FALSE:
+; If
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 278..279 }
+    mov rcx, 1
+    add rax, rcx
+    mov rcx, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 286..287 }
+    cmp rax, rcx
+    setl al
+    movzx rax, al
+    cmp rax, 0
+    je .7_IF_FALSE
+    mov qword [rbp-24], 0
+    jmp .6_END_IF
+.7_IF_FALSE:
+; Else If
+    mov rax, qword [rbp-16] ; Token { kind: Ident, value: Some("b"), span: 321..322 }
+    mov rcx, 2
+    imul rax, rcx
+    mov rcx, 6
+    cmp rax, rcx
+    sete al
+    movzx rax, al
+    cmp rax, 0
+    je .8_ELIF_FALSE
+; If
+    mov rax, 0
+    cmp rax, 0
+    je .A_IF_FALSE
+    mov qword [rbp-24], 1
+    jmp .9_END_IF
+.A_IF_FALSE:
+; Else If
+    mov rax, qword [rbp-24] ; Token { kind: Ident, value: Some("ans"), span: 392..395 }
+    mov rcx, 2
+    add rax, rcx
+    mov rcx, 0
+    cmp rax, rcx
+    setg al
+    movzx rax, al
+    cmp rax, 0
+    je .B_ELIF_FALSE
+    mov rax, qword [rbp-24] ; Token { kind: Ident, value: Some("ans"), span: 424..427 }
+    mov rcx, 10
+    add rax, rcx
+    mov qword [rbp-24], rax
+    jmp .9_END_IF
+.B_ELIF_FALSE:
+.9_END_IF:
+    jmp .6_END_IF
+.8_ELIF_FALSE:
+.6_END_IF:
+; Return
+    mov rax, qword [rbp-24] ; Token { kind: Ident, value: Some("ans"), span: 461..464 }
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
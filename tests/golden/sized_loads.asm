@@ -0,0 +1,43 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+main:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 48
+    mov byte [rbp-1], 200 ; Ident('a')
+    movzx rax, byte [rbp-1] ; Token { kind: Ident, value: Some("a"), span: 161..162 }
+    mov byte [rbp-2], al ; Ident('d')
+    movzx rax, byte [rbp-2] ; Token { kind: Ident, value: Some("d"), span: 181..182 }
+    mov dword [rbp-6], eax ; Ident('h')
+    mov rax, 300
+    neg rax
+    mov word [rbp-8], ax ; Ident('b')
+    mov dword [rbp-12], 100000 ; Ident('c')
+    movsx rax, word [rbp-8] ; Token { kind: Ident, value: Some("b"), span: 249..250 }
+    mov qword [rbp-20], rax ; Ident('e')
+    movsxd rax, dword [rbp-12] ; Token { kind: Ident, value: Some("c"), span: 269..270 }
+    mov qword [rbp-28], rax ; Ident('f')
+    mov rax, qword [rbp-20] ; Token { kind: Ident, value: Some("e"), span: 290..291 }
+    mov rcx, qword [rbp-28] ; Token { kind: Ident, value: Some("f"), span: 294..295 }
+    add rax, rcx
+    mov rcx, 1000
+    cqo
+    idiv rcx
+    mov qword [rbp-36], rax ; Ident('g')
+; Return
+    mov rax, qword [rbp-36] ; Token { kind: Ident, value: Some("g"), span: 316..317 }
+    mov rcx, 55
+    sub rax, rcx
+    mov rax, rax
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
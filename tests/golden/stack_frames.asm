@@ -0,0 +1,35 @@
+global _start
+section .text
+_start:
+    push rbp
+    mov rbp, rsp
+    call main
+    mov rdi, rax
+    mov rax, 60
+    syscall
+spill_i64_u8:
+    push rbp
+    mov rbp, rsp
+    sub rsp, 16
+    mov qword [rbp-8], rdi
+    mov byte [rbp-9], sil
+; Return
+    mov rax, qword [rbp-8] ; Token { kind: Ident, value: Some("a"), span: 154..155 }
+    jmp .spill_i64_u8_RETURN
+.spill_i64_u8_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret
+main:
+    push rbp
+    mov rbp, rsp
+; Return
+    mov rax, 40
+    mov rcx, 2
+    add rax, rcx
+    mov rax, rax
+    jmp .main_RETURN
+.main_RETURN:
+    mov rsp, rbp
+    pop rbp
+    ret